@@ -0,0 +1,194 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::Span;
+use syn::{Ident, LitStr};
+
+use crate::{
+    include::{self, Format},
+    shared::Setting,
+    smoller::{self, SmolEntry, SmolItem, SmollerOpts},
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Syntax(syn::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => fmt::Display::fmt(e, f),
+            Self::Syntax(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Syntax(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<syn::Error> for Error {
+    fn from(e: syn::Error) -> Self {
+        Self::Syntax(e)
+    }
+}
+
+/// Generates the same code as `smoller_strings!`, to be written to a file and `include!`d.
+///
+/// ```no_run
+/// // build.rs
+/// smoller_str_build::Builder::new("Methods")
+///     .values(["GET", "POST"])
+///     .file("methods.txt")?
+///     .setting(r#"lookup = "phf""#)?
+///     .write_to_out_dir("methods.rs")?;
+/// # Ok::<(), smoller_str_build::Error>(())
+/// ```
+///
+/// ```ignore
+/// // lib.rs
+/// include!(concat!(env!("OUT_DIR"), "/methods.rs"));
+/// ```
+///
+/// The output only depends on the input, so it can also be checked in.
+#[derive(Debug, Clone)]
+pub struct Builder {
+    name: String,
+    items: Vec<SmolItem>,
+    settings: Vec<String>,
+    files: Vec<PathBuf>,
+}
+
+impl Builder {
+    /// Starts a new type called `name`, with its builtin values in `{name}Repr`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            items: Vec::new(),
+            settings: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    pub fn value(mut self, value: &str) -> Self {
+        self.items
+            .push(SmolItem::new(LitStr::new(value, Span::call_site()), None));
+        self
+    }
+
+    /// Adds a value with an explicit variant name.
+    pub fn named_value(mut self, value: &str, ident: &str) -> Result<Self, Error> {
+        let ident = syn::parse_str::<Ident>(ident)?;
+        self.items.push(SmolItem::new(
+            LitStr::new(value, Span::call_site()),
+            Some(ident),
+        ));
+        Ok(self)
+    }
+
+    pub fn values<I>(self, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        values
+            .into_iter()
+            .fold(self, |builder, value| builder.value(value.as_ref()))
+    }
+
+    /// Adds the values from a file in any of the formats of `include_smoller_strings!`, picked
+    /// from its extension.
+    pub fn file(self, path: impl AsRef<Path>) -> Result<Self, Error> {
+        let format = Format::from_path(path.as_ref());
+        self.read(path.as_ref(), format)
+    }
+
+    /// Adds the values from a file in the given format: `"lines"`, `"json"`, `"csv"` or
+    /// `"toml"`.
+    pub fn file_with_format(self, path: impl AsRef<Path>, format: &str) -> Result<Self, Error> {
+        let format = Format::parse(&LitStr::new(format, Span::call_site()))?;
+        self.read(path.as_ref(), format)
+    }
+
+    fn read(mut self, path: &Path, format: Format) -> Result<Self, Error> {
+        let file = LitStr::new(&path.display().to_string(), Span::call_site());
+        let items = include::read(path, format, &file)?;
+        self.items.extend(items);
+        self.files.push(path.to_owned());
+        Ok(self)
+    }
+
+    /// Adds an argument as it would be written in `#[smoller_str(...)]`, such as
+    /// `lookup = "phf"` or `case_insensitive`. `parse_prefix = "trie"` is best left out for
+    /// large sets, whose trie would be most of the generated code.
+    ///
+    /// Unlike the macros, the generated code doesn't follow the features of `smoller_str`:
+    /// the `Serialize` and `Deserialize` impls are only written with the `serde` setting, and
+    /// the type's own searcher with the `search` setting.
+    pub fn setting(mut self, setting: &str) -> Result<Self, Error> {
+        syn::parse_str::<Setting>(setting)?;
+        self.settings.push(setting.to_owned());
+        Ok(self)
+    }
+
+    /// Generates the formatted source code.
+    pub fn generate(&self) -> Result<String, Error> {
+        let name = syn::parse_str::<Ident>(&self.name)?;
+        let settings = self
+            .settings
+            .iter()
+            .map(|setting| syn::parse_str::<Setting>(setting))
+            .collect::<syn::Result<Vec<_>>>()?;
+        let opts = SmollerOpts::from_settings(settings)?;
+        let entries = self.items.iter().cloned().map(SmolEntry::String).collect();
+        let tokens = smoller::smoller_func(opts, name, entries)?;
+        let file = syn::parse2::<syn::File>(tokens)?;
+        Ok(format!(
+            "// @generated by smoller_str_build, do not edit.\n\n{}",
+            prettyplease::unparse(&file)
+        ))
+    }
+
+    /// Writes the generated code to `path`, leaving the file untouched if it is up to date.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let code = self.generate()?;
+        let path = path.as_ref();
+        if std::fs::read_to_string(path).is_ok_and(|existing| existing == code) {
+            return Ok(());
+        }
+        std::fs::write(path, code)?;
+        Ok(())
+    }
+
+    /// Writes the generated code to `file_name` in `OUT_DIR`, and tells cargo to rerun the
+    /// build script when one of the input files changes.
+    pub fn write_to_out_dir(&self, file_name: &str) -> Result<PathBuf, Error> {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "`OUT_DIR` is not set, is this running in a build script?",
+            )
+        })?;
+        let path = PathBuf::from(out_dir).join(file_name);
+        self.write(&path)?;
+        for file in &self.files {
+            println!("cargo:rerun-if-changed={}", file.display());
+        }
+        Ok(path)
+    }
+}
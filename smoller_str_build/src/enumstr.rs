@@ -0,0 +1,975 @@
+use std::collections::HashMap;
+
+use crate::{lookup::Lookup, prefix::ParsePrefix, shared::*};
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    spanned::Spanned, Attribute, Data, DeriveInput, Error, Ident, LitStr, Result, Type, Visibility,
+};
+
+/// Options shared by `#[enum_str(...)]` and `#[smoller_str(...)]`.
+#[derive(Debug, Clone, Default)]
+pub struct EnumStrOpts {
+    pub lookup: Lookup,
+    pub parse_prefix: ParsePrefix,
+    pub case_insensitive: bool,
+    pub rename_all: Option<RenameAll>,
+    /// Implement `Serialize` and `Deserialize`, which needs the `serde` feature of `smoller_str`.
+    pub serde: bool,
+    /// Give the type its own searcher, which needs the `search` feature of `smoller_str`.
+    pub search: bool,
+}
+
+impl EnumStrOpts {
+    /// Applies `setting` if it is an `EnumStr` option, returning whether it was used.
+    pub fn apply(&mut self, setting: &Setting) -> Result<bool> {
+        if setting.name == "lookup" {
+            self.lookup = Lookup::parse(&setting.lit_str()?)?;
+        } else if setting.name == "parse_prefix" {
+            self.parse_prefix = ParsePrefix::parse(&setting.lit_str()?)?;
+        } else if setting.name == "case_insensitive" {
+            self.case_insensitive = setting.bool()?;
+        } else if setting.name == "rename_all" {
+            self.rename_all = Some(RenameAll::parse(&setting.lit_str()?)?);
+        } else if setting.name == "serde" {
+            self.serde = setting.bool()?;
+        } else if setting.name == "search" {
+            self.search = setting.bool()?;
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    pub fn apply_attrs(&mut self, attrs: &[Attribute]) -> Result<()> {
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("enum_str")) {
+            for setting in attr.parse_args_with(parse_settings)? {
+                if !self.apply(&setting)? {
+                    return Err(Error::new_spanned(setting.name, "invalid argument"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A `#[nested]` variant, holding a value of another `EnumStr` type.
+pub struct Nested {
+    pub ident: Ident,
+    pub ty: Type,
+    /// The prefix of the strings of the nested values, if any.
+    pub prefix: Option<LitStr>,
+    /// The number of value variants declared before it.
+    pub position: usize,
+}
+
+/// A variant of an `EnumStrInput`, in declaration order.
+enum Segment<'a> {
+    Value(usize, &'a Ident, &'a Value),
+    Nested(usize, &'a Nested),
+}
+
+pub struct EnumStrInput<'a> {
+    pub variants: Vec<(Ident, Value)>,
+    /// Their values go among those of `variants`, in declaration order.
+    pub nested: Vec<Nested>,
+    pub ident: &'a Ident,
+    pub vis: &'a Visibility,
+    pub opts: EnumStrOpts,
+}
+
+impl<'a> EnumStrInput<'a> {
+    pub fn new(
+        variants: Vec<(Ident, Value)>,
+        nested: Vec<Nested>,
+        ident: &'a Ident,
+        vis: &'a Visibility,
+        opts: EnumStrOpts,
+    ) -> Self {
+        Self {
+            variants,
+            nested,
+            ident,
+            vis,
+            opts,
+        }
+    }
+}
+
+/// The parts of the `EnumStr` impl that change when there are `#[nested]` variants.
+struct NestedTokens {
+    new_body: TokenStream,
+    prefix_body: TokenStream,
+    from_index: TokenStream,
+    values: TokenStream,
+    aliases: TokenStream,
+    count: TokenStream,
+    to_str: Vec<TokenStream>,
+    to_aliases: Vec<TokenStream>,
+    to_index: Vec<TokenStream>,
+    /// Checks at compile time that every value parses back into itself.
+    check: TokenStream,
+}
+
+impl EnumStrInput<'_> {
+    /// The values of the nested variants go where they are declared, each taking the place of
+    /// all the values of its type.
+    ///
+    /// Their strings are only known once the nested types are compiled, so the flattened
+    /// `VALUES` and the checks for collisions are computed in `const` blocks.
+    fn expand_nested(&self, keys: &[(&LitStr, &Ident)]) -> NestedTokens {
+        NestedTokens {
+            new_body: self.nested_new(keys),
+            prefix_body: self.nested_parse_prefix(keys),
+            from_index: self.nested_from_index(),
+            values: self.nested_values(),
+            aliases: self.nested_aliases(),
+            count: self.start(self.variants.len(), &self.nested),
+            to_str: self.nested_to_str(),
+            to_aliases: self.nested_to_aliases(),
+            to_index: self.nested_to_index(),
+            check: self.nested_check(),
+        }
+    }
+
+    /// The variants in declaration order.
+    fn segments(&self) -> Vec<Segment<'_>> {
+        let mut segments = Vec::with_capacity(self.variants.len() + self.nested.len());
+        let mut nested = self.nested.iter().enumerate().peekable();
+        for (i, (ident, value)) in self.variants.iter().enumerate() {
+            while let Some((j, inner)) = nested.next_if(|(_, inner)| inner.position == i) {
+                segments.push(Segment::Nested(j, inner));
+            }
+            segments.push(Segment::Value(i, ident, value));
+        }
+        segments.extend(nested.map(|(j, inner)| Segment::Nested(j, inner)));
+        segments
+    }
+
+    /// The index of the first value after `values` value variants and the `nested` variants.
+    fn start(&self, values: usize, nested: &[Nested]) -> TokenStream {
+        let tys = nested.iter().map(|nested| &nested.ty);
+        quote!(#values #( + <#tys as smoller_str::EnumStr>::COUNT )*)
+    }
+
+    /// The index of the (first) value of `segment`.
+    fn segment_start(&self, segment: &Segment) -> TokenStream {
+        match *segment {
+            Segment::Value(i, ..) => {
+                let before = self.nested.partition_point(|nested| nested.position <= i);
+                self.start(i, &self.nested[..before])
+            }
+            Segment::Nested(j, nested) => self.start(nested.position, &self.nested[..j]),
+        }
+    }
+
+    /// The body of `new`: the own values, then each nested type in turn.
+    fn nested_new(&self, keys: &[(&LitStr, &Ident)]) -> TokenStream {
+        let ty = self.ident;
+        let case_insensitive = self.opts.case_insensitive;
+        let own = (!keys.is_empty()).then(|| {
+            let lookup = self.opts.lookup.expand(ty, keys, case_insensitive);
+            quote! {
+                const fn lookup(s: &str) -> Option<#ty> {
+                    #lookup
+                }
+                if let Some(value) = lookup(s) {
+                    return Some(value);
+                }
+            }
+        });
+        let nested_new = self.nested.iter().map(|nested| {
+            let Nested {
+                ident, ty: inner, ..
+            } = nested;
+            let found = |s: TokenStream| {
+                quote! {
+                    if let Some(value) = #inner::__nested_new(#s) {
+                        return Some(#ty::#ident(value));
+                    }
+                }
+            };
+            match &nested.prefix {
+                None => found(quote!(s)),
+                Some(prefix) => {
+                    let found = found(quote!(s.split_at(#prefix.len()).1));
+                    quote! {
+                        if smoller_str::lookup::has_prefix(s, #prefix, #case_insensitive) {
+                            #found
+                        }
+                    }
+                }
+            }
+        });
+        quote! {
+            #own
+            #(#nested_new)*
+            None
+        }
+    }
+
+    /// The body of `parse_prefix_bytes`, keeping the longest match of all the types.
+    fn nested_parse_prefix(&self, keys: &[(&LitStr, &Ident)]) -> TokenStream {
+        let ty = self.ident;
+        let case_insensitive = self.opts.case_insensitive;
+        let prefix = self.opts.parse_prefix.expand(ty, keys, case_insensitive);
+        let nested_prefix = self.nested.iter().map(|nested| {
+            let Nested {
+                ident, ty: inner, ..
+            } = nested;
+            let longest = |bytes: TokenStream| {
+                quote! {
+                    if let Some((value, rest)) = #inner::__nested_parse_prefix_bytes(#bytes) {
+                        let longer = match best {
+                            Some((_, best_rest)) => rest.len() < best_rest.len(),
+                            None => true,
+                        };
+                        if longer {
+                            best = Some((#ty::#ident(value), rest));
+                        }
+                    }
+                }
+            };
+            match &nested.prefix {
+                None => longest(quote!(bytes)),
+                Some(prefix) => {
+                    let longest = longest(quote!(bytes.split_at(#prefix.len()).1));
+                    quote! {
+                        if smoller_str::lookup::has_prefix_bytes(bytes, #prefix, #case_insensitive) {
+                            #longest
+                        }
+                    }
+                }
+            }
+        });
+        quote! {
+            let mut best: Option<(Self, &[u8])> = #prefix;
+            #(#nested_prefix)*
+            best
+        }
+    }
+
+    /// The arm of `from_index`, finding the variant that `index` falls in.
+    fn nested_from_index(&self) -> TokenStream {
+        let ty = self.ident;
+        let segments = self.segments();
+        let from_index = segments.iter().map(|segment| {
+            let start = self.segment_start(segment);
+            match segment {
+                Segment::Value(_, ident, _) => quote! {
+                    if index == #start {
+                        return Some(#ty::#ident);
+                    }
+                },
+                Segment::Nested(
+                    _,
+                    Nested {
+                        ident, ty: inner, ..
+                    },
+                ) => quote! {
+                    if index < #start + <#inner as smoller_str::EnumStr>::COUNT {
+                        return match #inner::__nested_from_index(index - (#start)) {
+                            Some(value) => Some(#ty::#ident(value)),
+                            None => None,
+                        };
+                    }
+                },
+            }
+        });
+        quote! {
+            _ => {
+                #(#from_index)*
+                None
+            }
+        }
+    }
+
+    /// The arms of `index` for all the variants.
+    fn nested_to_index(&self) -> Vec<TokenStream> {
+        let ty = self.ident;
+        self.segments()
+            .iter()
+            .map(|segment| {
+                let start = self.segment_start(segment);
+                match segment {
+                    Segment::Value(_, ident, _) => quote!( #ty::#ident => #start, ),
+                    Segment::Nested(
+                        _,
+                        Nested {
+                            ident, ty: inner, ..
+                        },
+                    ) => {
+                        quote!( #ty::#ident(value) => #start + #inner::__nested_index(value), )
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// The arms of `as_str` for the nested variants.
+    fn nested_to_str(&self) -> Vec<TokenStream> {
+        let ty = self.ident;
+        self.nested
+            .iter()
+            .map(|nested| {
+                let Nested {
+                    ident,
+                    ty: inner,
+                    prefix,
+                    ..
+                } = nested;
+                match prefix {
+                    None => quote!( #ty::#ident(value) => #inner::__nested_as_str(value), ),
+                    Some(prefix) => {
+                        let prefixed = prefixed_strs(
+                            prefix,
+                            quote!(<#inner as smoller_str::EnumStr>::COUNT),
+                            |i| quote!(#inner::__nested_as_str(&<#inner as smoller_str::EnumStr>::VALUES[#i])),
+                        );
+                        quote! {
+                            #ty::#ident(value) => {
+                                #prefixed
+                                smoller_str::nested::str_at(&BUFFER, &OFFSETS, #inner::__nested_index(value))
+                            }
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// The arms of `aliases` for the nested variants.
+    ///
+    /// The aliases of one value under a prefix are not next to each other in the buffer of
+    /// `ALIASES`, so they get a buffer of their own, grouped by value, with `STARTS` telling
+    /// where the aliases of each value start.
+    fn nested_to_aliases(&self) -> Vec<TokenStream> {
+        let ty = self.ident;
+        self.nested
+            .iter()
+            .map(|nested| {
+                let Nested {
+                    ident,
+                    ty: inner,
+                    prefix,
+                    ..
+                } = nested;
+                let prefix = match prefix {
+                    None => return quote!( #ty::#ident(value) => #inner::__nested_aliases(value), ),
+                    Some(prefix) => prefix,
+                };
+                let prefixed =
+                    prefixed_strs(prefix, quote!(GROUPED.len()), |i| quote!(GROUPED[#i]));
+                quote! {
+                    #ty::#ident(value) => {
+                        const VALUES: &[#inner] = <#inner as smoller_str::EnumStr>::VALUES;
+                        const STARTS: [usize; VALUES.len() + 1] = {
+                            let mut starts = [0; VALUES.len() + 1];
+                            let mut i = 0;
+                            while i < VALUES.len() {
+                                starts[i + 1] = starts[i] + #inner::__nested_aliases(&VALUES[i]).len();
+                                i += 1;
+                            }
+                            starts
+                        };
+                        const GROUPED: [&str; STARTS[VALUES.len()]] = {
+                            let mut grouped = [""; STARTS[VALUES.len()]];
+                            let mut i = 0;
+                            while i < VALUES.len() {
+                                let aliases = #inner::__nested_aliases(&VALUES[i]);
+                                let mut j = 0;
+                                while j < aliases.len() {
+                                    grouped[STARTS[i] + j] = aliases[j];
+                                    j += 1;
+                                }
+                                i += 1;
+                            }
+                            grouped
+                        };
+                        #prefixed
+                        const ALIASES: &[&str] =
+                            &smoller_str::nested::strs::<COUNT>(&BUFFER, &OFFSETS);
+                        let i = #inner::__nested_index(value);
+                        let (_, aliases) = ALIASES.split_at(STARTS[i]);
+                        aliases.split_at(STARTS[i + 1] - STARTS[i]).0
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// A value to fill the arrays with before they are written.
+    fn placeholder(&self) -> TokenStream {
+        let ty = self.ident;
+        match self.variants.first() {
+            Some((ident, _)) => quote!(#ty::#ident),
+            None => {
+                let Nested {
+                    ident, ty: inner, ..
+                } = &self.nested[0];
+                quote!(#ty::#ident(<#inner as smoller_str::EnumStr>::VALUES[0]))
+            }
+        }
+    }
+
+    /// `VALUES`, filled in through `from_index`.
+    fn nested_values(&self) -> TokenStream {
+        let ty = self.ident;
+        let placeholder = self.placeholder();
+        quote! {
+            &{
+                let mut values = [#placeholder; <#ty as smoller_str::EnumStr>::COUNT];
+                let mut i = 0;
+                while i < values.len() {
+                    values[i] = match #ty::from_index(i) {
+                        Some(value) => value,
+                        None => unreachable!(),
+                    };
+                    i += 1;
+                }
+                values
+            }
+        }
+    }
+
+    /// `ALIASES` in declaration order, with the prefix in front of those of nested types.
+    fn nested_aliases(&self) -> TokenStream {
+        let ty = self.ident;
+        let placeholder = self.placeholder();
+        let own_alias_count = self
+            .variants
+            .iter()
+            .map(|(_, val)| val.aliases.len())
+            .sum::<usize>();
+        let nested_tys = self.nested.iter().map(|nested| &nested.ty);
+        let fill = self.segments().into_iter().map(|segment| match segment {
+            Segment::Value(_, ident, val) => {
+                let aliases = &val.aliases;
+                quote! {
+                    #(
+                        aliases[at] = (#aliases, #ty::#ident);
+                        at += 1;
+                    )*
+                }
+            }
+            Segment::Nested(_, nested) => {
+                let Nested {
+                    ident,
+                    ty: inner,
+                    prefix,
+                    ..
+                } = nested;
+                let (prefixed, alias) = match prefix {
+                    None => (None, quote!(nested[i].0)),
+                    Some(prefix) => {
+                        let prefixed = prefixed_strs(
+                            prefix,
+                            quote!(<#inner as smoller_str::EnumStr>::ALIASES.len()),
+                            |i| quote!(<#inner as smoller_str::EnumStr>::ALIASES[#i].0),
+                        );
+                        let alias = quote!(smoller_str::nested::str_at(&BUFFER, &OFFSETS, i));
+                        (Some(prefixed), alias)
+                    }
+                };
+                quote! {
+                    {
+                        #prefixed
+                        let nested = <#inner as smoller_str::EnumStr>::ALIASES;
+                        let mut i = 0;
+                        while i < nested.len() {
+                            aliases[at] = (#alias, #ty::#ident(nested[i].1));
+                            at += 1;
+                            i += 1;
+                        }
+                    }
+                }
+            }
+        });
+        quote! {
+            &{
+                let mut aliases = [
+                    ("", #placeholder);
+                    #own_alias_count #( + <#nested_tys as smoller_str::EnumStr>::ALIASES.len() )*
+                ];
+                let mut at = 0;
+                #(#fill)*
+                aliases
+            }
+        }
+    }
+
+    /// Fails to compile, naming the string, if a value or alias of a nested variant does not
+    /// parse back into itself.
+    ///
+    /// The own values are checked by the derive already, so only the nested variants can
+    /// collide, and each is checked on its own for the error to point at it.
+    fn nested_check(&self) -> TokenStream {
+        let ty = self.ident;
+        let before = format!("two values of `{ty}` have the same string `");
+        let after = "`, or it is the alias of another";
+        let checks = self.nested.iter().map(
+            |Nested {
+                 ident, ty: inner, ..
+             }| {
+                quote_spanned! {inner.span()=>
+                    const _: () = {
+                        const fn collision() -> Option<&'static str> {
+                            let values = <#ty as smoller_str::EnumStr>::VALUES;
+                            let mut i = 0;
+                            while i < values.len() {
+                                let s = #ty::as_str(&values[i]);
+                                match #ty::new(s) {
+                                    Some(value) if #ty::index(&value) == i => {}
+                                    _ if matches!(values[i], #ty::#ident(_)) => return Some(s),
+                                    _ => {}
+                                }
+                                i += 1;
+                            }
+                            let aliases = <#ty as smoller_str::EnumStr>::ALIASES;
+                            let mut i = 0;
+                            while i < aliases.len() {
+                                let (s, expected) = aliases[i];
+                                match #ty::new(s) {
+                                    Some(value) if #ty::index(&value) == #ty::index(&expected) => {}
+                                    _ if matches!(expected, #ty::#ident(_)) => return Some(s),
+                                    _ => {}
+                                }
+                                i += 1;
+                            }
+                            None
+                        }
+                        const COLLISION: Option<&str> = collision();
+                        const PARTS: [&str; 3] = [
+                            #before,
+                            match COLLISION {
+                                Some(s) => s,
+                                None => "",
+                            },
+                            #after,
+                        ];
+                        const MSG: [u8; smoller_str::nested::buffer_len("", &PARTS)] =
+                            smoller_str::nested::buffer("", &PARTS);
+                        if COLLISION.is_some() {
+                            panic!("{}", smoller_str::nested::message(&MSG));
+                        }
+                    };
+                }
+            },
+        );
+        quote!(#(#checks)*)
+    }
+}
+
+/// Defines `BUFFER` and `OFFSETS`, holding each of the `count` strings `str_at(i)` after
+/// `prefix`, for [`smoller_str::nested::str_at`].
+fn prefixed_strs(
+    prefix: &LitStr,
+    count: TokenStream,
+    str_at: impl FnOnce(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let str_at = str_at(quote!(i));
+    quote! {
+        const COUNT: usize = #count;
+        const STRS: [&str; COUNT] = {
+            let mut strs = [""; COUNT];
+            let mut i = 0;
+            while i < COUNT {
+                strs[i] = #str_at;
+                i += 1;
+            }
+            strs
+        };
+        const BUFFER: [u8; smoller_str::nested::buffer_len(#prefix, &STRS)] =
+            smoller_str::nested::buffer(#prefix, &STRS);
+        const OFFSETS: [usize; COUNT + 1] = smoller_str::nested::offsets(#prefix, &STRS);
+    }
+}
+
+impl<'a> ToTokens for EnumStrInput<'a> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let variants = &self.variants;
+        let ty_ident = self.ident;
+
+        let keys = variants
+            .iter()
+            .flat_map(|(ident, val)| val.all().map(move |lit| (lit, ident)))
+            .collect::<Vec<_>>();
+        let case_insensitive = self.opts.case_insensitive;
+        let lookup = self.opts.lookup.expand(ty_ident, &keys, case_insensitive);
+        let prefix = self
+            .opts
+            .parse_prefix
+            .expand(ty_ident, &keys, case_insensitive);
+        let str_eq = |lhs: TokenStream, rhs: TokenStream| {
+            if case_insensitive {
+                quote!(#lhs.eq_ignore_ascii_case(#rhs))
+            } else {
+                quote!(#lhs == #rhs)
+            }
+        };
+        let eq_str = str_eq(quote!(self.as_str()), quote!(other));
+        let eq_ref_str = str_eq(quote!(self.as_str()), quote!(*other));
+        let ref_eq_str = str_eq(quote!(#ty_ident::as_str(self)), quote!(other));
+        let str_eq_self = str_eq(quote!(self), quote!(other.as_str()));
+        let to_str = variants.iter().map(|(ident, val)| {
+            let value = &val.value;
+            quote!( #ty_ident::#ident => #value, )
+        });
+        let to_aliases = variants.iter().map(|(ident, val)| {
+            let aliases = &val.aliases;
+            quote!( #ty_ident::#ident => &[#(#aliases),*], )
+        });
+        let alias_values = variants.iter().flat_map(|(ident, val)| {
+            val.aliases
+                .iter()
+                .map(move |alias| quote! { (#alias, Self::#ident) })
+        });
+
+        let vis = self.vis;
+
+        let literal_values = variants.iter().map(|(ident, _)| quote! { Self::#ident });
+        let count = variants.len();
+        let mut to_index = variants
+            .iter()
+            .enumerate()
+            .map(|(i, (ident, _))| quote!( #ty_ident::#ident => #i, ))
+            .collect::<Vec<_>>();
+        let from_index = variants
+            .iter()
+            .enumerate()
+            .map(|(i, (ident, _))| quote!( #i => Some(#ty_ident::#ident), ));
+
+        let mut new_body = lookup;
+        let mut prefix_body = prefix;
+        let mut from_index = quote! {
+            #( #from_index )*
+            _ => None,
+        };
+        let mut values = quote!(&[ #( #literal_values ),* ]);
+        let mut alias_list = quote!(&[ #( #alias_values ),* ]);
+        let mut count = quote!(#count);
+        let mut nested_to_str = Vec::new();
+        let mut nested_to_aliases = Vec::new();
+        let mut check = None;
+        if !self.nested.is_empty() {
+            let nested = self.expand_nested(&keys);
+            new_body = nested.new_body;
+            prefix_body = nested.prefix_body;
+            from_index = nested.from_index;
+            values = nested.values;
+            alias_list = nested.aliases;
+            count = nested.count;
+            nested_to_str = nested.to_str;
+            nested_to_aliases = nested.to_aliases;
+            to_index = nested.to_index;
+            check = Some(nested.check);
+        }
+
+        let impl_serde = self.opts.serde.then(|| {
+            quote! {
+                impl smoller_str::serde::Serialize for #ty_ident {
+                    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where
+                        S: smoller_str::serde::Serializer,
+                    {
+                        smoller_str::serde::serialize(#ty_ident::as_str(self), serializer)
+                    }
+                }
+
+                impl<'de> smoller_str::serde::Deserialize<'de> for #ty_ident {
+                    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                    where
+                        D: smoller_str::serde::Deserializer<'de>,
+                    {
+                        smoller_str::serde::deserialize_enum_str(deserializer)
+                    }
+                }
+            }
+        });
+
+        let impl_case_sensitive =
+            (!case_insensitive).then(|| quote!(impl smoller_str::CaseSensitive for #ty_ident {}));
+
+        let impl_search = self.opts.search.then(|| {
+            quote! {
+                fn searcher() -> &'static smoller_str::search::Searcher<Self> {
+                    static SEARCHER: std::sync::OnceLock<smoller_str::search::Searcher<#ty_ident>> =
+                        std::sync::OnceLock::new();
+                    SEARCHER.get_or_init(smoller_str::search::Searcher::new)
+                }
+            }
+        });
+
+        let impls = quote! {
+
+            impl #ty_ident {
+                #vis const fn new(s: &str) -> Option<Self> {
+                    #new_body
+                }
+                #vis const fn as_str(&self) -> &'static str {
+                    match self {
+                        #(#to_str)*
+                        #(#nested_to_str)*
+                    }
+                }
+                /// The longest value (or alias) that `s` starts with, and the rest of `s`.
+                #vis const fn parse_prefix(s: &str) -> Option<(Self, &str)> {
+                    match #ty_ident::parse_prefix_bytes(s.as_bytes()) {
+                        Some((value, rest)) => Some((value, s.split_at(s.len() - rest.len()).1)),
+                        None => None,
+                    }
+                }
+                /// The longest value (or alias) that `bytes` starts with, and the rest of `bytes`.
+                #vis const fn parse_prefix_bytes(bytes: &[u8]) -> Option<(Self, &[u8])> {
+                    #prefix_body
+                }
+                /// The position of this value in declaration order.
+                #vis const fn index(&self) -> usize {
+                    match self {
+                        #(#to_index)*
+                    }
+                }
+                /// The value at `index` in declaration order.
+                #vis const fn from_index(index: usize) -> Option<Self> {
+                    match index {
+                        #from_index
+                    }
+                }
+                /// The other spellings that parse into this value.
+                #vis const fn aliases(&self) -> &'static [&'static str] {
+                    match self {
+                        #(#to_aliases)*
+                        #(#nested_to_aliases)*
+                    }
+                }
+            }
+
+            /// What the `#[nested]` variants of other types call, since those can be in a module
+            /// where the functions above are private.
+            #[doc(hidden)]
+            impl #ty_ident {
+                pub const fn __nested_new(s: &str) -> Option<Self> {
+                    #ty_ident::new(s)
+                }
+                pub const fn __nested_as_str(&self) -> &'static str {
+                    #ty_ident::as_str(self)
+                }
+                pub const fn __nested_parse_prefix_bytes(bytes: &[u8]) -> Option<(Self, &[u8])> {
+                    #ty_ident::parse_prefix_bytes(bytes)
+                }
+                pub const fn __nested_index(&self) -> usize {
+                    #ty_ident::index(self)
+                }
+                pub const fn __nested_from_index(index: usize) -> Option<Self> {
+                    #ty_ident::from_index(index)
+                }
+                pub const fn __nested_aliases(&self) -> &'static [&'static str] {
+                    #ty_ident::aliases(self)
+                }
+            }
+
+            #impl_case_sensitive
+
+            impl smoller_str::nested::Values for #ty_ident {
+                type EnumStr = Self;
+            }
+
+            impl smoller_str::EnumStr for #ty_ident {
+                const VALUES: &'static [Self] = #values;
+                const ALIASES: &'static [(&'static str, Self)] = #alias_list;
+                const CASE_INSENSITIVE: bool = #case_insensitive;
+                const COUNT: usize = #count;
+                fn as_str(&self) -> &'static str {
+                    #ty_ident::as_str(self)
+                }
+                fn aliases(&self) -> &'static [&'static str] {
+                    #ty_ident::aliases(self)
+                }
+                fn index(&self) -> usize {
+                    #ty_ident::index(self)
+                }
+                fn from_index(index: usize) -> Option<Self> {
+                    #ty_ident::from_index(index)
+                }
+                fn parse_prefix(s: &str) -> Option<(Self, &str)> {
+                    #ty_ident::parse_prefix(s)
+                }
+                fn parse_prefix_bytes(bytes: &[u8]) -> Option<(Self, &[u8])> {
+                    #ty_ident::parse_prefix_bytes(bytes)
+                }
+                #impl_search
+            }
+
+            impl std::fmt::Display for #ty_ident {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str(#ty_ident::as_str(self))
+                }
+            }
+
+            impl std::str::FromStr for #ty_ident {
+                type Err = ();
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    #ty_ident::new(s).ok_or(())
+                    // match s {
+                    //     #(to_variant_ok)*
+                    //     _ => Err(())
+                    // }
+                }
+            }
+
+            impl AsRef<str> for #ty_ident {
+                fn as_ref(&self) -> &str {
+                    #ty_ident::as_str(self)
+                }
+            }
+
+            impl PartialEq<str> for #ty_ident {
+                fn eq(&self, other: &str) -> bool {
+                    #eq_str
+                }
+            }
+
+            impl PartialEq<&'_ str> for #ty_ident {
+                fn eq(&self, other: &&str) -> bool {
+                    #eq_ref_str
+                }
+            }
+
+            impl PartialEq<str> for &'_ #ty_ident {
+                fn eq(&self, other: &str) -> bool {
+                    #ref_eq_str
+                }
+            }
+
+            impl PartialEq<#ty_ident> for str {
+                fn eq(&self, other: &#ty_ident) -> bool {
+                    #str_eq_self
+                }
+            }
+
+            #impl_serde
+
+            #check
+        };
+
+        tokens.extend(impls);
+    }
+}
+
+// #[derive(Debug)]
+// struct EnumStrDerive<'a> {
+//     body: &'a DataEnum,
+//     attrs: &'a [Attribute],
+//     vis: &'a Visibility,
+//     ident: &'a Ident,
+// }
+
+// impl<'a> EnumStrDerive<'a> {
+//     fn build(self) -> Result<TokenStream> {
+//         let variants = self.parse_variants()?;
+//         dbg!(&variants);
+//         dbg!(&self.attrs);
+//         // let to_variant_ok = variants
+//         //     .iter()
+//         //     .map(|(ident, val)| quote! { #val => Ok(#ty_ident::#ident), })
+//         //     .collect();
+
+//         Ok(EnumStrInput::new(variants, self.ident, self.vis).to_token_stream())
+//     }
+
+//     fn parse_variants(&self) -> Result<Vec<(&'a Ident, LitStr)>> {
+//         let mut variants = Vec::with_capacity(self.body.variants.len());
+//         for v in &self.body.variants {
+//             if v.fields != Fields::Unit {
+//                 return Err(Error::new_spanned(
+//                     v,
+//                     "only unit variants can be used with EnumStr",
+//                 ));
+//             }
+//             let attr = match v.attrs.iter().find(|attr| attr.path.is_ident("value")) {
+//                 Some(a) => a,
+//                 None => {
+//                     return Err(Error::new_spanned(
+//                         v,
+//                         "`#[value(\"...\")]` attribute required",
+//                     ))
+//                 }
+//             };
+
+//             let val: LitStr = attr.parse_args()?;
+//             variants.push((&v.ident, val));
+//         }
+//         Ok(variants)
+//     }
+// }
+
+pub fn derive(node: &DeriveInput) -> Result<TokenStream> {
+    let mut opts = EnumStrOpts::default();
+    opts.apply_attrs(&node.attrs)?;
+    derive_with_opts(node, opts)
+}
+
+pub fn derive_with_opts(node: &DeriveInput, opts: EnumStrOpts) -> Result<TokenStream> {
+    let enum_node = match &node.data {
+        Data::Enum(e) => e,
+        _ => {
+            return Err(Error::new_spanned(
+                node,
+                "EnumStr can only be used on enums",
+            ))
+        }
+    };
+
+    let variants = parse_variants(enum_node.variants.iter(), opts.rename_all)?;
+    let mut value_variants = Vec::with_capacity(variants.len());
+    let mut nested = Vec::new();
+    for variant in variants {
+        match variant {
+            VariantData::Field(ident, _) => {
+                return Err(Error::new_spanned(ident, "only unit variants are allowed"))
+            }
+            VariantData::Prefix(ident, ..) => {
+                return Err(Error::new_spanned(
+                    ident,
+                    "`#[value(prefix = \"...\")]` can only be used with `#[smoller_str]`",
+                ))
+            }
+            VariantData::Value(ident, value) => {
+                value_variants.push((ident.clone(), value));
+            }
+            VariantData::Nested(ident, prefix, field) => nested.push(Nested {
+                ident: ident.clone(),
+                ty: field.ty.clone(),
+                prefix,
+                position: value_variants.len(),
+            }),
+        }
+    }
+
+    if opts.case_insensitive {
+        check_case_collisions(&value_variants)?;
+    }
+
+    Ok(EnumStrInput::new(value_variants, nested, &node.ident, &node.vis, opts).to_token_stream())
+}
+
+/// With `case_insensitive`, two values that only differ by case could never both be parsed.
+fn check_case_collisions(variants: &[(Ident, Value)]) -> Result<()> {
+    let mut seen = HashMap::<String, &LitStr>::new();
+    for value in variants.iter().flat_map(|(_, value)| value.all()) {
+        let first = seen.insert(value.value().to_ascii_lowercase(), value);
+        if let Some(first) = first.filter(|first| first.value() != value.value()) {
+            let mut err = Error::new_spanned(
+                value,
+                format!(
+                    "`{}` only differs by case from `{}`",
+                    value.value(),
+                    first.value()
+                ),
+            );
+            err.combine(Error::new_spanned(first, "first defined here"));
+            return Err(err);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Variant names for values given without an explicit `= Ident`.
+use convert_case::{Case, Casing};
+use syn::{Error, Ident, LitStr, Result};
+
+/// The case style of generated variant names, set with `ident_case = "..."`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdentCase {
+    #[default]
+    UpperCamel,
+    ScreamingSnake,
+    Snake,
+    Camel,
+}
+
+impl IdentCase {
+    pub fn parse(lit: &LitStr) -> Result<Self> {
+        match lit.value().as_str() {
+            "UpperCamel" | "PascalCase" => Ok(Self::UpperCamel),
+            "ScreamingSnake" | "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            "Snake" | "snake_case" => Ok(Self::Snake),
+            "Camel" | "camelCase" => Ok(Self::Camel),
+            _ => Err(Error::new_spanned(
+                lit,
+                "expected one of `\"UpperCamel\"`, `\"ScreamingSnake\"`, `\"Snake\"` or `\"Camel\"`",
+            )),
+        }
+    }
+
+    fn case(self) -> Case {
+        match self {
+            Self::UpperCamel => Case::UpperCamel,
+            Self::ScreamingSnake => Case::ScreamingSnake,
+            Self::Snake => Case::Snake,
+            Self::Camel => Case::Camel,
+        }
+    }
+}
+
+/// The ASCII spelling of common accented Latin letters.
+fn transliterate(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' => "i",
+        'ł' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ś' | 'š' | 'ş' => "s",
+        'ß' => "ss",
+        'ť' | 'ţ' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
+
+/// Names for the punctuation in values such as `"+"` or `"<="`, which have no words.
+fn symbol_name(c: char) -> Option<&'static str> {
+    Some(match c {
+        '+' => "plus",
+        '-' => "minus",
+        '*' => "star",
+        '/' => "slash",
+        '\\' => "backslash",
+        '%' => "percent",
+        '^' => "caret",
+        '!' => "bang",
+        '&' => "and",
+        '|' => "or",
+        '=' => "eq",
+        '<' => "lt",
+        '>' => "gt",
+        '@' => "at",
+        '.' => "dot",
+        ',' => "comma",
+        ';' => "semi",
+        ':' => "colon",
+        '#' => "pound",
+        '$' => "dollar",
+        '?' => "question",
+        '~' => "tilde",
+        '(' => "l paren",
+        ')' => "r paren",
+        '[' => "l bracket",
+        ']' => "r bracket",
+        '{' => "l brace",
+        '}' => "r brace",
+        '\'' => "quote",
+        '"' => "double quote",
+        '`' => "backtick",
+        '_' => "underscore",
+        _ => return None,
+    })
+}
+
+/// The words of `value`: runs of letters and digits, with accents removed where possible.
+///
+/// Everything else separates words, and case changes within a run are split later by
+/// `convert_case`. A value without any words is spelled out by the names of its symbols.
+fn words(value: &str) -> Option<String> {
+    let mut words = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_alphanumeric() {
+            match transliterate(c.to_lowercase().next().unwrap_or(c)) {
+                Some(ascii) if c.is_uppercase() => words.push_str(&ascii.to_uppercase()),
+                Some(ascii) => words.push_str(ascii),
+                None => words.push(c),
+            }
+        } else if !words.ends_with(' ') {
+            words.push(' ');
+        }
+    }
+    if words.trim().is_empty() {
+        let names = value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(symbol_name)
+            .collect::<Option<Vec<_>>>()?;
+        words = names.join(" ");
+    }
+    Some(words)
+}
+
+/// The variant name for `lit` in the given case.
+///
+/// Names starting with a digit get a leading `_`, and keywords a trailing `_`.
+pub fn variant_ident(lit: &LitStr, case: IdentCase) -> Result<Ident> {
+    let value = lit.value();
+    let error = || {
+        Error::new_spanned(
+            lit,
+            format!("cannot make a variant name from `{value}`, name it with `= Ident`"),
+        )
+    };
+    let mut name = words(&value).ok_or_else(error)?.to_case(case.case());
+    if name.is_empty() {
+        return Err(error());
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    if syn::parse_str::<Ident>(&name).is_err() {
+        name.push('_');
+    }
+    syn::parse_str::<Ident>(&name)
+        .map(|ident| Ident::new(&ident.to_string(), lit.span()))
+        .map_err(|_| error())
+}
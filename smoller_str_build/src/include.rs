@@ -0,0 +1,337 @@
+//! `include_smoller_strings!`: reading the strings from a file.
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    Error, Ident, LitStr, Result, Token,
+};
+
+use serde::{
+    de::{self, value::MapAccessDeserializer, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::{
+    shared::{parse_settings, Setting},
+    smoller::{self, SmolEntry, SmolItem, SmollerOpts},
+};
+
+/// `include_smoller_strings!(Name, "file" $(, setting)*)`.
+pub struct Input {
+    name: Ident,
+    _comma: Token![,],
+    file: LitStr,
+    settings: Vec<Setting>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        let _comma = input.parse()?;
+        let file = input.parse()?;
+        let settings = if input.is_empty() {
+            Vec::new()
+        } else {
+            let _: Token![,] = input.parse()?;
+            parse_settings(input)?
+        };
+        Ok(Input {
+            name,
+            _comma,
+            file,
+            settings,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Lines,
+    Json,
+    Csv,
+    Toml,
+}
+
+impl Format {
+    pub fn parse(lit: &LitStr) -> Result<Self> {
+        match lit.value().as_str() {
+            "lines" => Ok(Self::Lines),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "toml" => Ok(Self::Toml),
+            _ => Err(Error::new_spanned(
+                lit,
+                "expected one of `\"lines\"`, `\"json\"`, `\"csv\"` or `\"toml\"`",
+            )),
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::Json,
+            Some("csv") => Self::Csv,
+            Some("toml") => Self::Toml,
+            _ => Self::Lines,
+        }
+    }
+}
+
+/// Resolves the path given to the macro.
+///
+/// Relative paths are relative to the manifest directory of the crate being compiled, and a
+/// leading `$OUT_DIR` is replaced by the output directory of its build script.
+fn resolve(file: &LitStr) -> Result<PathBuf> {
+    let value = file.value();
+    if let Some(rest) = value.strip_prefix("$OUT_DIR") {
+        let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+            Error::new_spanned(
+                file,
+                "`$OUT_DIR` is only set for crates with a build script",
+            )
+        })?;
+        return Ok(PathBuf::from(out_dir).join(rest.trim_start_matches(['/', '\\'])));
+    }
+    let path = PathBuf::from(value);
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) if path.is_relative() => Ok(PathBuf::from(dir).join(path)),
+        _ => Ok(path),
+    }
+}
+
+/// Parses the lines of a strings file.
+///
+/// Each line is one value, either raw or as a quoted Rust string. A raw line is taken as it is,
+/// `=` included, so naming the variant with `= Ident` needs the value to be quoted. Blank lines
+/// and lines starting with `#` are ignored, and lines starting with `///` document the value
+/// that follows them:
+///
+/// ```text
+/// # HTTP methods
+/// /// Fetches a resource.
+/// GET
+/// "M-SEARCH" = MSearch
+/// --color=auto
+/// ```
+fn parse_lines(contents: &str, file: &LitStr) -> Result<Vec<SmolItem>> {
+    let error = |line: usize, msg: &dyn Display| {
+        Error::new_spanned(file, format!("{}:{}: {msg}", file.value(), line + 1))
+    };
+
+    let mut items = Vec::new();
+    let mut docs = Vec::new();
+    let mut last_doc = 0;
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.push(LitStr::new(doc, file.span()));
+            last_doc = i;
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let mut item = if line.starts_with('"') {
+            syn::parse_str::<SmolItem>(line).map_err(|e| error(i, &e))?
+        } else {
+            SmolItem::new(LitStr::new(line, file.span()), None)
+        };
+        if let Some(ident) = &mut item.ident {
+            ident.set_span(file.span());
+        }
+        item.value.set_span(file.span());
+        item.docs = std::mem::take(&mut docs);
+        items.push(item);
+    }
+    if !docs.is_empty() {
+        return Err(error(last_doc, &"doc comment is not followed by a value"));
+    }
+    Ok(items)
+}
+
+/// A value in the structured formats: either just the string, or a table with the
+/// variant name, aliases and documentation.
+enum Entry {
+    Value(String),
+    Full(FullEntry),
+}
+
+// Not `#[serde(untagged)]`, whose error for a bad table doesn't say which field is wrong.
+impl<'de> Deserialize<'de> for Entry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct EntryVisitor;
+
+        impl<'de> Visitor<'de> for EntryVisitor {
+            type Value = Entry;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string or a table with a `value`")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> std::result::Result<Entry, E> {
+                Ok(Entry::Value(value.to_owned()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> std::result::Result<Entry, A::Error> {
+                FullEntry::deserialize(MapAccessDeserializer::new(map)).map(Entry::Full)
+            }
+        }
+
+        deserializer.deserialize_any(EntryVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FullEntry {
+    value: String,
+    #[serde(default)]
+    ident: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    doc: Option<String>,
+}
+
+/// A TOML file lists its values in a top-level `values` array.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TomlFile {
+    values: Vec<Entry>,
+}
+
+/// A CSV file has a header row naming its columns; only `value` is required, and several
+/// aliases are separated by `|`.
+#[derive(Deserialize)]
+struct CsvRecord {
+    value: String,
+    #[serde(default)]
+    ident: Option<String>,
+    #[serde(default)]
+    aliases: Option<String>,
+    #[serde(default)]
+    doc: Option<String>,
+}
+
+impl From<CsvRecord> for FullEntry {
+    fn from(record: CsvRecord) -> Self {
+        let aliases = record.aliases.unwrap_or_default();
+        Self {
+            value: record.value,
+            ident: record.ident.filter(|ident| !ident.is_empty()),
+            aliases: aliases
+                .split('|')
+                .filter(|alias| !alias.is_empty())
+                .map(str::to_owned)
+                .collect(),
+            doc: record.doc.filter(|doc| !doc.is_empty()),
+        }
+    }
+}
+
+impl Entry {
+    fn into_item(self, file: &LitStr) -> Result<SmolItem> {
+        let entry = match self {
+            Self::Value(value) => return Ok(SmolItem::new(LitStr::new(&value, file.span()), None)),
+            Self::Full(entry) => entry,
+        };
+        let ident = match entry.ident {
+            Some(ident) => {
+                let mut ident = syn::parse_str::<Ident>(&ident).map_err(|_| {
+                    Error::new_spanned(
+                        file,
+                        format!("{}: `{ident}` is not a valid identifier", file.value()),
+                    )
+                })?;
+                ident.set_span(file.span());
+                Some(ident)
+            }
+            None => None,
+        };
+        let mut item = SmolItem::new(LitStr::new(&entry.value, file.span()), ident);
+        item.aliases = entry
+            .aliases
+            .iter()
+            .map(|alias| LitStr::new(alias, file.span()))
+            .collect();
+        item.docs = entry
+            .doc
+            .iter()
+            .flat_map(|doc| doc.lines())
+            .map(|line| LitStr::new(&format!(" {line}"), file.span()))
+            .collect();
+        Ok(item)
+    }
+}
+
+fn parse_contents(contents: &str, format: Format, file: &LitStr) -> Result<Vec<SmolItem>> {
+    let error = |e: &dyn Display| Error::new_spanned(file, format!("{}: {e}", file.value()));
+    let entries = match format {
+        Format::Lines => return parse_lines(contents, file),
+        Format::Json => serde_json::from_str::<Vec<Entry>>(contents).map_err(|e| error(&e))?,
+        Format::Toml => {
+            toml::from_str::<TomlFile>(contents)
+                .map_err(|e| error(&e))?
+                .values
+        }
+        Format::Csv => csv::Reader::from_reader(contents.as_bytes())
+            .deserialize::<CsvRecord>()
+            .map(|record| Ok(Entry::Full(record.map_err(|e| error(&e))?.into())))
+            .collect::<Result<_>>()?,
+    };
+    entries
+        .into_iter()
+        .map(|entry| entry.into_item(file))
+        .collect()
+}
+
+/// Reads the strings from the file at `path`, which the user called `file`.
+pub fn read(path: &Path, format: Format, file: &LitStr) -> Result<Vec<SmolItem>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| Error::new_spanned(file, format!("Unable to read {path:?}: {e}")))?;
+    parse_contents(&contents, format, file)
+}
+
+/// Expands `include_smoller_strings!`, with its settings applied on top of `opts`.
+pub fn include(input: Input, opts: SmollerOpts) -> Result<TokenStream> {
+    let Input {
+        name,
+        file,
+        settings,
+        ..
+    } = input;
+    let input_file = resolve(&file)?;
+
+    let mut format = Format::from_path(&input_file);
+    let mut smoller_settings = Vec::with_capacity(settings.len());
+    for setting in settings {
+        if setting.name == "format" {
+            format = Format::parse(&setting.lit_str()?)?;
+        } else {
+            smoller_settings.push(setting);
+        }
+    }
+    let opts = opts.with_settings(smoller_settings)?;
+
+    let entries = read(&input_file, format, &file)?
+        .into_iter()
+        .map(SmolEntry::String)
+        .collect();
+
+    let mut tokens = smoller::smoller_func(opts, name, entries)?;
+
+    // Makes the compiler track the file, so that changing it triggers a rebuild.
+    let tracked = input_file.to_string_lossy();
+    tokens.extend(quote! {
+        const _: &[u8] = include_bytes!(#tracked);
+    });
+    Ok(tokens)
+}
@@ -0,0 +1,22 @@
+//! Code generation for `smoller_str`.
+//!
+//! This is the implementation of the `smoller_str_macro` macros, and can also be used from a
+//! build script through [`Builder`] to generate a type once instead of expanding a macro on
+//! every build.
+mod builder;
+#[doc(hidden)]
+pub mod enumstr;
+#[doc(hidden)]
+pub mod ident;
+#[doc(hidden)]
+pub mod include;
+#[doc(hidden)]
+pub mod lookup;
+#[doc(hidden)]
+pub mod prefix;
+#[doc(hidden)]
+pub mod shared;
+#[doc(hidden)]
+pub mod smoller;
+
+pub use builder::{Builder, Error};
@@ -0,0 +1,256 @@
+//! Backends for the body of the generated `const fn new(s: &str) -> Option<Self>`.
+use std::collections::{BTreeMap, HashSet};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Error, Ident, LitByteStr, LitStr, Result};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lookup {
+    /// A single `match` over the input bytes.
+    #[default]
+    Match,
+    /// A compile-time perfect hash table.
+    Phf,
+    /// A `match` on the input length, then on the bytes of the values with that length.
+    Length,
+}
+
+impl Lookup {
+    pub fn parse(lit: &LitStr) -> Result<Self> {
+        match lit.value().as_str() {
+            "match" => Ok(Self::Match),
+            "phf" => Ok(Self::Phf),
+            "length" => Ok(Self::Length),
+            _ => Err(Error::new_spanned(
+                lit,
+                "expected one of `\"match\"`, `\"phf\"` or `\"length\"`",
+            )),
+        }
+    }
+
+    /// Expands to an expression of type `Option<#ty>` looking up `s: &str` in `keys`.
+    ///
+    /// If the same key appears more than once the first variant wins.
+    pub fn expand(self, ty: &Ident, keys: &[(&LitStr, &Ident)], ignore_case: bool) -> TokenStream {
+        match self {
+            Self::Match => expand_match(ty, keys, ignore_case),
+            Self::Length => expand_length(ty, keys, ignore_case),
+            Self::Phf => expand_phf(ty, keys, ignore_case),
+        }
+    }
+}
+
+fn byte_str(lit: &LitStr) -> LitByteStr {
+    LitByteStr::new(lit.value().as_bytes(), lit.span())
+}
+
+fn expand_match(ty: &Ident, keys: &[(&LitStr, &Ident)], ignore_case: bool) -> TokenStream {
+    if ignore_case {
+        let checks = keys.iter().map(|(lit, ident)| {
+            let bytes = byte_str(lit);
+            quote! {
+                if smoller_str::lookup::eq_ignore_ascii_case(s.as_bytes(), #bytes) {
+                    return Some(#ty::#ident);
+                }
+            }
+        });
+        return quote! {{
+            #(#checks)*
+            None
+        }};
+    }
+    let arms = keys.iter().map(|(lit, ident)| {
+        let bytes = byte_str(lit);
+        quote! { #bytes => Some(#ty::#ident), }
+    });
+    quote! {
+        match s.as_bytes() {
+            #(#arms)*
+            _ => None,
+        }
+    }
+}
+
+fn expand_length(ty: &Ident, keys: &[(&LitStr, &Ident)], ignore_case: bool) -> TokenStream {
+    let mut by_len = BTreeMap::<usize, Vec<(&LitStr, &Ident)>>::new();
+    for &(lit, ident) in keys {
+        by_len.entry(lit.value().len()).or_default().push((lit, ident));
+    }
+    let arms = by_len.iter().map(|(len, keys)| {
+        let inner = expand_match(ty, keys, ignore_case);
+        quote! { #len => #inner, }
+    });
+    quote! {
+        match s.len() {
+            #(#arms)*
+            _ => None,
+        }
+    }
+}
+
+/// Bucket load factor of the hash-and-displace table, as in the `phf` crate.
+const LAMBDA: usize = 5;
+
+struct Table {
+    seed: u64,
+    displacements: Vec<(u32, u32)>,
+    /// For every slot, the index of the key stored there.
+    slots: Vec<usize>,
+}
+
+/// Seeds tried before giving up on a perfect hash table.
+const MAX_SEEDS: u64 = 1000;
+
+pub struct Hashes {
+    pub g: u32,
+    pub f1: u32,
+    pub f2: u32,
+}
+
+/// Must match `smoller_str::lookup::hash`, which `tests/lookup.rs` checks.
+pub fn hash(bytes: &[u8], seed: u64, ignore_case: bool) -> Hashes {
+    let mut h = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for &b in bytes {
+        let b = if ignore_case {
+            b.to_ascii_lowercase()
+        } else {
+            b
+        };
+        h ^= b as u64;
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    }
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^= h >> 33;
+    Hashes {
+        g: (h >> 43) as u32,
+        f1: ((h >> 22) & 0x1f_ffff) as u32,
+        f2: (h & 0x3f_ffff) as u32,
+    }
+}
+
+fn displace(f1: u32, f2: u32, d1: u32, d2: u32) -> u32 {
+    d2.wrapping_add(f1.wrapping_mul(d1)).wrapping_add(f2)
+}
+
+impl Table {
+    fn build(keys: &[Vec<u8>], ignore_case: bool) -> Option<Self> {
+        (0..MAX_SEEDS).find_map(|seed| Self::try_build(keys, seed, ignore_case))
+    }
+
+    fn try_build(keys: &[Vec<u8>], seed: u64, ignore_case: bool) -> Option<Self> {
+        let len = keys.len();
+        let hashes = keys
+            .iter()
+            .map(|k| hash(k, seed, ignore_case))
+            .collect::<Vec<_>>();
+
+        let bucket_count = len.div_ceil(LAMBDA);
+        let mut buckets = vec![Vec::new(); bucket_count];
+        for (i, h) in hashes.iter().enumerate() {
+            buckets[h.g as usize % bucket_count].push(i);
+        }
+        let mut order = (0..bucket_count).collect::<Vec<_>>();
+        order.sort_by_key(|&b| std::cmp::Reverse(buckets[b].len()));
+
+        let mut displacements = vec![(0, 0); bucket_count];
+        let mut slots = vec![None; len];
+        let mut taken = Vec::with_capacity(LAMBDA);
+        'buckets: for b in order {
+            let bucket = &buckets[b];
+            if bucket.is_empty() {
+                break;
+            }
+            for d1 in 0..len as u32 {
+                for d2 in 0..len as u32 {
+                    taken.clear();
+                    let fits = bucket.iter().all(|&key| {
+                        let h = &hashes[key];
+                        let slot = displace(h.f1, h.f2, d1, d2) as usize % len;
+                        if slots[slot].is_some() || taken.contains(&slot) {
+                            return false;
+                        }
+                        taken.push(slot);
+                        true
+                    });
+                    if fits {
+                        for (&key, &slot) in bucket.iter().zip(&taken) {
+                            slots[slot] = Some(key);
+                        }
+                        displacements[b] = (d1, d2);
+                        continue 'buckets;
+                    }
+                }
+            }
+            return None;
+        }
+
+        Some(Self {
+            seed,
+            displacements,
+            slots: slots.into_iter().map(Option::unwrap).collect(),
+        })
+    }
+}
+
+fn expand_phf(ty: &Ident, all_keys: &[(&LitStr, &Ident)], ignore_case: bool) -> TokenStream {
+    let mut seen = HashSet::new();
+    let keys = all_keys
+        .iter()
+        .filter(|(lit, _)| {
+            let value = lit.value();
+            seen.insert(if ignore_case {
+                value.to_ascii_lowercase()
+            } else {
+                value
+            })
+        })
+        .collect::<Vec<_>>();
+    if keys.is_empty() {
+        return quote! { None };
+    }
+
+    let key_bytes = keys
+        .iter()
+        .map(|(lit, _)| lit.value().into_bytes())
+        .collect::<Vec<_>>();
+    // Without a table, the lookup is still correct, only not constant time.
+    let Some(table) = Table::build(&key_bytes, ignore_case) else {
+        return expand_length(ty, all_keys, ignore_case);
+    };
+
+    let len = keys.len();
+    let bucket_count = table.displacements.len();
+    let seed = table.seed;
+    let displacements = table
+        .displacements
+        .iter()
+        .map(|(d1, d2)| quote! { (#d1, #d2) });
+    let eq = if ignore_case {
+        quote!(smoller_str::lookup::eq_ignore_ascii_case)
+    } else {
+        quote!(smoller_str::lookup::bytes_eq)
+    };
+    let slots = table.slots.iter().map(|&i| {
+        let (lit, ident) = keys[i];
+        let bytes = byte_str(lit);
+        quote! { (#bytes, #ty::#ident) }
+    });
+
+    quote! {{
+        const DISPLACEMENTS: [(u32, u32); #bucket_count] = [#(#displacements),*];
+        const SLOTS: [(&[u8], #ty); #len] = [#(#slots),*];
+
+        let bytes = s.as_bytes();
+        let (key, value) =
+            SLOTS[smoller_str::lookup::slot(bytes, #seed, #ignore_case, &DISPLACEMENTS, #len)];
+        if #eq(key, bytes) {
+            Some(value)
+        } else {
+            None
+        }
+    }}
+}
@@ -0,0 +1,141 @@
+//! Backends for the body of the generated `const fn parse_prefix_bytes`.
+use std::collections::BTreeMap;
+
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use syn::{Error, Ident, LitStr, Result};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParsePrefix {
+    /// A loop over a table of the values, which stays small for large sets.
+    #[default]
+    Scan,
+    /// A `match` on each byte in turn, which is faster but grows with the total length of
+    /// the values.
+    Trie,
+}
+
+impl ParsePrefix {
+    pub fn parse(lit: &LitStr) -> Result<Self> {
+        match lit.value().as_str() {
+            "scan" => Ok(Self::Scan),
+            "trie" => Ok(Self::Trie),
+            _ => Err(Error::new_spanned(lit, "expected `\"scan\"` or `\"trie\"`")),
+        }
+    }
+
+    /// Expands to an expression of type `Option<(#ty, &[u8])>` finding the longest of `keys`
+    /// that `bytes: &[u8]` starts with.
+    ///
+    /// If the same key appears more than once the first variant wins.
+    pub fn expand(self, ty: &Ident, keys: &[(&LitStr, &Ident)], ignore_case: bool) -> TokenStream {
+        match self {
+            Self::Scan => expand_scan(ty, keys, ignore_case),
+            Self::Trie => expand_trie(ty, keys, ignore_case),
+        }
+    }
+}
+
+fn expand_scan(ty: &Ident, keys: &[(&LitStr, &Ident)], ignore_case: bool) -> TokenStream {
+    if keys.is_empty() {
+        return quote!(None);
+    }
+    let (lits, idents): (Vec<_>, Vec<_>) = keys.iter().copied().unzip();
+    quote! {{
+        const KEYS: &[(&str, #ty)] = &[ #( (#lits, #ty::#idents) ),* ];
+        let mut best: Option<(#ty, usize)> = None;
+        let mut i = 0;
+        while i < KEYS.len() {
+            let (key, value) = KEYS[i];
+            if smoller_str::lookup::has_prefix_bytes(bytes, key, #ignore_case) {
+                match best {
+                    Some((_, len)) if len >= key.len() => {}
+                    _ => best = Some((value, key.len())),
+                }
+            }
+            i += 1;
+        }
+        match best {
+            Some((value, len)) => Some((value, bytes.split_at(len).1)),
+            None => None,
+        }
+    }}
+}
+
+#[derive(Default)]
+struct Node<'a> {
+    /// The variant of the value ending at this node.
+    value: Option<&'a Ident>,
+    children: BTreeMap<u8, Node<'a>>,
+}
+
+impl<'a> Node<'a> {
+    fn insert(&mut self, bytes: &[u8], ident: &'a Ident) {
+        let node = bytes
+            .iter()
+            .fold(self, |node, &b| node.children.entry(b).or_default());
+        // As with the lookups, the first variant with a given value wins.
+        node.value.get_or_insert(ident);
+    }
+
+    /// Expands to statements recording the longest match in `best`, with `depth` bytes of
+    /// `bytes` already matched.
+    fn expand(&self, ty: &Ident, depth: usize, ignore_case: bool) -> TokenStream {
+        let value = self.value.map(|ident| {
+            quote! { best = Some((#ty::#ident, #depth)); }
+        });
+        if self.children.is_empty() {
+            return quote! { #value };
+        }
+        let byte = quote!(smoller_str::lookup::byte_at(bytes, #depth, #ignore_case));
+        let mut arms = self.children.iter().map(|(&b, child)| {
+            (
+                Literal::byte_character(b),
+                child.expand(ty, depth + 1, ignore_case),
+            )
+        });
+        let branch = if self.children.len() == 1 {
+            let (b, child) = arms.next().unwrap();
+            quote! {
+                if let Some(#b) = #byte {
+                    #child
+                }
+            }
+        } else {
+            let arms = arms.map(|(b, child)| quote! { Some(#b) => { #child } });
+            quote! {
+                match #byte {
+                    #(#arms)*
+                    _ => {}
+                }
+            }
+        };
+        quote! {
+            #value
+            #branch
+        }
+    }
+}
+
+fn expand_trie(ty: &Ident, keys: &[(&LitStr, &Ident)], ignore_case: bool) -> TokenStream {
+    let mut root = Node::default();
+    for &(lit, ident) in keys {
+        let mut value = lit.value();
+        if ignore_case {
+            value.make_ascii_lowercase();
+        }
+        root.insert(value.as_bytes(), ident);
+    }
+    if root.value.is_none() && root.children.is_empty() {
+        return quote!(None);
+    }
+    let body = root.expand(ty, 0, ignore_case);
+    quote! {{
+        let mut best: Option<(#ty, usize)> = None;
+        #body
+        match best {
+            Some((value, len)) => Some((value, bytes.split_at(len).1)),
+            None => None,
+        }
+    }}
+}
@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use convert_case::{Boundary, Case, Converter};
+use quote::ToTokens;
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Error, Expr, ExprLit, Field, Fields, Ident, Lit, LitStr, Result, Token, Variant,
+};
+
+// pub enum VariantKind<'a> {
+//     Known(&'a Ident, LitStr),
+//     Unknown(&'a Ident, )
+// }
+#[allow(dead_code)]
+pub enum VariantData<'a> {
+    Value(&'a Ident, Value),
+    Field(&'a Ident, &'a Field),
+    /// `#[value(prefix = "...")]` on a tuple variant, which holds the rest of the string.
+    Prefix(&'a Ident, LitStr, &'a Field),
+    /// `#[nested]` or `#[nested(prefix = "...")]` on a tuple variant holding another `EnumStr`.
+    Nested(&'a Ident, Option<LitStr>, &'a Field),
+}
+
+/// Parses the arguments of `#[value(prefix = "...")]`.
+fn parse_prefix(input: ParseStream) -> Result<LitStr> {
+    let name: Ident = input.parse()?;
+    if name != "prefix" {
+        return Err(Error::new_spanned(name, "expected `prefix = \"...\"`"));
+    }
+    let _: Token![=] = input.parse()?;
+    let prefix: LitStr = input.parse()?;
+    let _: Option<Token![,]> = input.parse()?;
+    if prefix.value().is_empty() {
+        return Err(Error::new_spanned(prefix, "the prefix cannot be empty"));
+    }
+    Ok(prefix)
+}
+
+/// The arguments of `#[value("...", alias = "...", ...)]`.
+#[derive(Debug, Clone)]
+pub struct Value {
+    /// The canonical spelling, returned by `as_str`.
+    pub value: LitStr,
+    /// Other spellings that parse into the same variant.
+    pub aliases: Vec<LitStr>,
+}
+
+impl Value {
+    pub fn new(value: LitStr) -> Self {
+        Self {
+            value,
+            aliases: Vec::new(),
+        }
+    }
+
+    /// The canonical value followed by the aliases.
+    pub fn all(&self) -> impl Iterator<Item = &LitStr> {
+        std::iter::once(&self.value).chain(&self.aliases)
+    }
+}
+
+impl Parse for Value {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut value = Self::new(input.parse()?);
+        while !input.is_empty() {
+            let _: Token![,] = input.parse()?;
+            if input.is_empty() {
+                break;
+            }
+            let name: Ident = input.parse()?;
+            if name != "alias" {
+                return Err(Error::new_spanned(name, "expected `alias = \"...\"`"));
+            }
+            let _: Token![=] = input.parse()?;
+            value.aliases.push(input.parse()?);
+        }
+        Ok(value)
+    }
+}
+
+/// `rename_all = "..."`: derives the values of variants without `#[value]` from their names,
+/// with the same case names as serde.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenameAll(Case);
+
+impl RenameAll {
+    pub fn parse(lit: &LitStr) -> Result<Self> {
+        let case = match lit.value().as_str() {
+            "lowercase" => Case::Flat,
+            "UPPERCASE" => Case::UpperFlat,
+            "PascalCase" => Case::Pascal,
+            "camelCase" => Case::Camel,
+            "snake_case" => Case::Snake,
+            "SCREAMING_SNAKE_CASE" => Case::ScreamingSnake,
+            "kebab-case" => Case::Kebab,
+            "SCREAMING-KEBAB-CASE" => Case::UpperKebab,
+            "Train-Case" => Case::Train,
+            "Title Case" => Case::Title,
+            _ => {
+                return Err(Error::new_spanned(
+                    lit,
+                    "expected one of `\"lowercase\"`, `\"UPPERCASE\"`, `\"PascalCase\"`, \
+                     `\"camelCase\"`, `\"snake_case\"`, `\"SCREAMING_SNAKE_CASE\"`, \
+                     `\"kebab-case\"`, `\"SCREAMING-KEBAB-CASE\"`, `\"Train-Case\"` or \
+                     `\"Title Case\"`",
+                ))
+            }
+        };
+        Ok(Self(case))
+    }
+
+    /// The value for the variant `ident`.
+    ///
+    /// Like serde, only an uppercase letter starts a new word, so `Http2Settings` is
+    /// `http2-settings` in `kebab-case`.
+    pub fn apply(self, ident: &Ident) -> LitStr {
+        let value = Converter::new()
+            .from_case(Case::Pascal)
+            .remove_boundaries(&Boundary::letter_digit())
+            .remove_boundary(Boundary::DigitLower)
+            .to_case(self.0)
+            .convert(ident.unraw().to_string());
+        LitStr::new(&value, ident.span())
+    }
+}
+
+pub fn parse_variants<'a>(
+    variants: impl Iterator<Item = &'a Variant>,
+    rename_all: Option<RenameAll>,
+) -> Result<Vec<VariantData<'a>>> {
+    let mut res = Vec::new();
+
+    for variant in variants {
+        let ident = &variant.ident;
+        let attr = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("value"));
+        let nested = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("nested"));
+        let data = match &variant.fields {
+            Fields::Named(_) => {
+                return Err(Error::new_spanned(variant, "named fields are not allowed"))
+            }
+            Fields::Unnamed(f) => {
+                if f.unnamed.len() != 1 {
+                    return Err(Error::new_spanned(
+                        f,
+                        "only a single tuple field is allowed",
+                    ));
+                }
+                match (attr, nested) {
+                    (Some(attr), Some(_)) => {
+                        return Err(Error::new_spanned(
+                            attr,
+                            "`#[value]` cannot be used on a `#[nested]` variant",
+                        ))
+                    }
+                    (None, Some(nested)) if nested.tokens.is_empty() => {
+                        VariantData::Nested(ident, None, &f.unnamed[0])
+                    }
+                    (None, Some(nested)) => VariantData::Nested(
+                        ident,
+                        Some(nested.parse_args_with(parse_prefix)?),
+                        &f.unnamed[0],
+                    ),
+                    (Some(attr), None) if attr.parse_args::<LitStr>().is_err() => {
+                        VariantData::Prefix(
+                            ident,
+                            attr.parse_args_with(parse_prefix)?,
+                            &f.unnamed[0],
+                        )
+                    }
+                    (Some(_), None) => {
+                        return Err(Error::new_spanned(
+                            f,
+                            "variant field cannot be used with `#[value(\"...\")]` attribute",
+                        ))
+                    }
+                    (None, None) => VariantData::Field(ident, &f.unnamed[0]),
+                }
+            }
+            Fields::Unit if nested.is_some() => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "a `#[nested]` variant must have a single field holding the nested type",
+                ))
+            }
+            Fields::Unit => {
+                let value = match (attr, rename_all) {
+                    (Some(attr), _) => attr.parse_args()?,
+                    (None, Some(rename_all)) => Value::new(rename_all.apply(ident)),
+                    (None, None) => {
+                        return Err(Error::new_spanned(
+                            variant,
+                            "missing `#[value(\"...\")]` attribute",
+                        ));
+                    }
+                };
+                VariantData::Value(ident, value)
+            }
+        };
+        res.push(data);
+    }
+    check_values(
+        res.iter()
+            .flat_map(|data| match data {
+                VariantData::Value(_, value) => Some(value.all()),
+                VariantData::Field(..) | VariantData::Prefix(..) | VariantData::Nested(..) => None,
+            })
+            .flatten(),
+    )?;
+    Ok(res)
+}
+
+/// Adds `err` to the errors reported so far.
+pub fn push_error(errors: &mut Option<Error>, err: Error) {
+    match errors {
+        Some(errors) => errors.combine(err),
+        None => *errors = Some(err),
+    }
+}
+
+/// Errors for `value`, which was already defined as `first`.
+pub fn duplicate_error(value: impl ToTokens, first: impl ToTokens, msg: String) -> Error {
+    let mut err = Error::new_spanned(value, msg);
+    err.combine(Error::new_spanned(first, "first defined here"));
+    err
+}
+
+/// Checks that no value or alias is empty or repeated, since the later of two equal values
+/// could never be parsed.
+pub fn check_values<'a>(values: impl IntoIterator<Item = &'a LitStr>) -> Result<()> {
+    let mut seen = HashMap::<String, &LitStr>::new();
+    let mut errors = None;
+    for lit in values {
+        let value = lit.value();
+        if value.is_empty() {
+            push_error(
+                &mut errors,
+                Error::new_spanned(lit, "values cannot be empty"),
+            );
+        } else if let Some(first) = seen.get(&value) {
+            let msg = format!("duplicate value `{value}`");
+            push_error(&mut errors, duplicate_error(lit, first, msg));
+        } else {
+            seen.insert(value, lit);
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+// fn parse_value_variant(variant: &Variant, allow_field: bool) -> Result<(&Ident, Option<LitStr>)> {
+//     if !allow_field && variant.fields != Fields::Unit {
+//         return Err(Error::new_spanned(v, "only unit variants are allowed"));
+//     }
+//     let attr = match v.attrs.iter().find(|attr| attr.path.is_ident("value")) {
+//         Some(a) => a,
+//         None => {
+//             return Err(Error::new_spanned(
+//                 v,
+//                 "`#[value(\"...\")]` attribute required",
+//             ))
+//         }
+//     };
+// }
+
+/// A single `name = value` or bare `name` argument of `#[smoller_str(...)]` or
+/// `#[enum_str(...)]`.
+pub struct Setting {
+    pub name: Ident,
+    pub value: Option<Expr>,
+}
+
+impl Setting {
+    pub fn bool(&self) -> Result<bool> {
+        match &self.value {
+            None => Ok(true),
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Bool(lit),
+                ..
+            })) => Ok(lit.value),
+            Some(e) => Err(Error::new_spanned(e, "expected a bool")),
+        }
+    }
+
+    pub fn lit_str(&self) -> Result<LitStr> {
+        match &self.value {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            })) => Ok(lit.clone()),
+            Some(e) => Err(Error::new_spanned(e, "expected a string literal")),
+            None => Err(Error::new_spanned(&self.name, "expected a string literal")),
+        }
+    }
+
+    pub fn ident(&self) -> Result<Ident> {
+        match &self.value {
+            Some(Expr::Path(path)) if path.qself.is_none() => path
+                .path
+                .get_ident()
+                .cloned()
+                .ok_or_else(|| Error::new_spanned(path, "expected an identifier")),
+            Some(e) => Err(Error::new_spanned(e, "expected an identifier")),
+            None => Err(Error::new_spanned(&self.name, "expected an identifier")),
+        }
+    }
+}
+
+impl Parse for Setting {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            let _: Token![=] = input.parse()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { name, value })
+    }
+}
+
+pub fn parse_settings(input: ParseStream) -> Result<Vec<Setting>> {
+    let settings: Punctuated<Setting, Token![,]> = input.parse_terminated(Setting::parse)?;
+    Ok(settings.into_iter().collect())
+}
@@ -0,0 +1,1397 @@
+use std::collections::HashMap;
+
+use crate::{
+    enumstr::{self, EnumStrOpts},
+    ident::{self, IdentCase},
+    shared::{
+        check_values, duplicate_error, parse_settings, parse_variants, push_error, Setting,
+        VariantData,
+    },
+};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Data, DeriveInput, Error, Expr, Fields, GenericArgument, Ident, Lit, LitStr, Meta,
+    MetaNameValue, Path, PathArguments, Result, Token, Type, Variant, Visibility,
+};
+
+// pub fn derive(node: &DeriveInput) -> Result<TokenStream> {
+//     let enum_node = match &node.data {
+//         Data::Enum(e) => e,
+//         _ => {
+//             return Err(Error::new_spanned(
+//                 node,
+//                 "SmollerStr can only be used on enums",
+//             ))
+//         }
+//     };
+
+//     let variants = parse_variants(enum_node.variants.iter())?;
+//     let mut value_variants = Vec::with_capacity(variants.len());
+//     for variant in variants {
+//         match variant {
+//             VariantData::Field(ident, _) => {
+//                 return Err(Error::new_spanned(ident, "only unit variants are allowed"))
+//             }
+//             VariantData::Value(ident, value) => {
+//                 value_variants.push((ident, value));
+//             }
+//         }
+//     }
+
+//     let val = EnumStrDerive {
+//         body: enum_node,
+//         attrs: &node.attrs,
+//         vis: &node.vis,
+//         ident: &node.ident,
+//     };
+//     // dbg!(&val);
+//     val.build()
+// }
+
+// pub struct SmollerInput {
+//     attrs: Vec<Attribute>,
+// }
+
+// impl Parse for SmollerInput {
+//     fn parse(input: ParseStream) -> Result<Self> {
+//         let attrs: Vec<Attribute> = input.call(Attribute::parse_outer)?;
+//     }
+// }
+
+// fn check_derive()
+
+/// The type holding the string of the `Unknown` variant.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Storage {
+    SmolStr,
+    #[default]
+    Arc,
+    Rc,
+    Box,
+    String,
+}
+
+impl Storage {
+    fn parse(ident: &Ident) -> Result<Self> {
+        Ok(if ident == "SmolStr" {
+            Self::SmolStr
+        } else if ident == "Arc" {
+            Self::Arc
+        } else if ident == "Rc" {
+            Self::Rc
+        } else if ident == "Box" {
+            Self::Box
+        } else if ident == "String" {
+            Self::String
+        } else {
+            return Err(Error::new_spanned(
+                ident,
+                "expected one of `SmolStr`, `Arc`, `Rc`, `Box` or `String`",
+            ));
+        })
+    }
+
+    fn ty(self) -> TokenStream {
+        match self {
+            Self::SmolStr => quote!(smoller_str::SmolStr),
+            Self::Arc => quote!(std::sync::Arc<str>),
+            Self::Rc => quote!(std::rc::Rc<str>),
+            Self::Box => quote!(Box<str>),
+            Self::String => quote!(String),
+        }
+    }
+
+    /// Recognizes the storage types among the types of `#[fallback]` variants.
+    fn from_type(ty: &Type) -> Option<Self> {
+        let Type::Path(path) = ty else {
+            return None;
+        };
+        let last = path.path.segments.last()?;
+        let holds_str = || match &last.arguments {
+            PathArguments::AngleBracketed(args) => {
+                matches!(args.args.first(), Some(GenericArgument::Type(ty)) if ty == &parse_quote!(str))
+            }
+            _ => false,
+        };
+        if last.ident == "SmolStr" {
+            Some(Self::SmolStr)
+        } else if last.ident == "String" {
+            Some(Self::String)
+        } else if last.ident == "Arc" && holds_str() {
+            Some(Self::Arc)
+        } else if last.ident == "Rc" && holds_str() {
+            Some(Self::Rc)
+        } else if last.ident == "Box" && holds_str() {
+            Some(Self::Box)
+        } else {
+            None
+        }
+    }
+
+    /// Expands to the `&str` in `s`, a reference to the field of a variant holding strings.
+    ///
+    /// `SmolStr` has no `AsRef<str>`, so it gets its own `as_str`.
+    fn as_str(storage: Option<Self>) -> TokenStream {
+        match storage {
+            Some(Self::SmolStr) => quote!(s.as_str()),
+            _ => quote!(AsRef::<str>::as_ref(s)),
+        }
+    }
+
+    /// Expands to the `is_heap_allocated` match arm for the `variant` holding unknown strings.
+    ///
+    /// An empty `String` or `Box<str>` holds no allocation. Without a known storage type,
+    /// the string is assumed to be on the heap.
+    fn is_heap_allocated_arm(storage: Option<Self>, variant: &Ident) -> TokenStream {
+        match storage {
+            Some(Self::SmolStr) => quote!(Self::#variant(s) => s.is_heap_allocated()),
+            Some(Self::String) => quote!(Self::#variant(s) => s.capacity() != 0),
+            Some(Self::Box) => quote!(Self::#variant(s) => !s.is_empty()),
+            _ => quote!(Self::#variant(_) => true),
+        }
+    }
+}
+
+/// How a `#[fallback(...)]` variant recognizes the strings it holds.
+enum FallbackRule {
+    Prefix(LitStr),
+    Suffix(LitStr),
+    /// The path to a `fn(&str) -> bool`.
+    With(syn::Path),
+}
+
+impl FallbackRule {
+    /// The rule of a `#[fallback]` attribute, or `None` for the catch-all.
+    fn parse(attr: &Attribute) -> Result<Option<Self>> {
+        if attr.tokens.is_empty() {
+            return Ok(None);
+        }
+        let mut settings = attr.parse_args_with(parse_settings)?.into_iter();
+        let (Some(setting), None) = (settings.next(), settings.next()) else {
+            return Err(Error::new_spanned(
+                attr,
+                "expected one of `prefix = \"...\"`, `suffix = \"...\"` or `with = path`",
+            ));
+        };
+        let name = &setting.name;
+        let rule = if name == "prefix" || name == "suffix" {
+            let lit = setting.lit_str()?;
+            if lit.value().is_empty() {
+                return Err(Error::new_spanned(
+                    lit,
+                    format!("the {name} cannot be empty"),
+                ));
+            }
+            if name == "prefix" {
+                Self::Prefix(lit)
+            } else {
+                Self::Suffix(lit)
+            }
+        } else if name == "with" {
+            match setting.value {
+                Some(Expr::Path(path)) if path.qself.is_none() => Self::With(path.path),
+                Some(e) => {
+                    return Err(Error::new_spanned(
+                        e,
+                        "expected the path to a `fn(&str) -> bool`",
+                    ))
+                }
+                None => {
+                    return Err(Error::new_spanned(
+                        name,
+                        "expected the path to a `fn(&str) -> bool`",
+                    ))
+                }
+            }
+        } else {
+            return Err(Error::new_spanned(name, "invalid argument"));
+        };
+        Ok(Some(rule))
+    }
+
+    /// Expands to whether the string `s` goes in the variant.
+    fn condition(&self, ignore_case: bool) -> TokenStream {
+        match self {
+            Self::Prefix(lit) => quote!(smoller_str::lookup::has_prefix(s, #lit, #ignore_case)),
+            Self::Suffix(lit) => quote!(smoller_str::lookup::has_suffix(s, #lit, #ignore_case)),
+            Self::With(path) => quote!((#path)(s)),
+        }
+    }
+}
+
+pub struct SmollerOpts {
+    pub impl_deref: bool,
+    pub no_derives: bool,
+    pub intern: bool,
+    pub storage: Storage,
+    /// The `storage = ...` setting, if one was given.
+    pub storage_setting: Option<Ident>,
+    /// With `unknown = "reject"`, parsing fails for strings that are not builtin values.
+    pub reject_unknown: bool,
+    /// The case of the variant names made up by `smoller_strings!`.
+    pub ident_case: IdentCase,
+    pub enum_str: EnumStrOpts,
+}
+
+impl SmollerOpts {
+    pub fn from_settings(settings: impl IntoIterator<Item = Setting>) -> Result<Self> {
+        Self::default().with_settings(settings)
+    }
+
+    /// Applies `settings` on top of these options.
+    pub fn with_settings(self, settings: impl IntoIterator<Item = Setting>) -> Result<Self> {
+        let mut opts = self;
+        let mut reject = None;
+        for setting in settings {
+            let name = &setting.name;
+            if name == "deref" {
+                opts.impl_deref = setting.bool()?;
+            } else if name == "derives" {
+                opts.no_derives = !setting.bool()?;
+            } else if name == "intern" {
+                opts.intern = setting.bool()?;
+            } else if name == "storage" {
+                let ident = setting.ident()?;
+                opts.storage = Storage::parse(&ident)?;
+                opts.storage_setting = Some(ident);
+            } else if name == "unknown" {
+                let lit = setting.lit_str()?;
+                opts.reject_unknown = match lit.value().as_str() {
+                    "store" => false,
+                    "reject" => {
+                        reject = Some(lit);
+                        true
+                    }
+                    _ => {
+                        return Err(Error::new_spanned(
+                            lit,
+                            "expected `\"store\"` or `\"reject\"`",
+                        ))
+                    }
+                };
+            } else if name == "ident_case" {
+                opts.ident_case = IdentCase::parse(&setting.lit_str()?)?;
+            } else if !opts.enum_str.apply(&setting)? {
+                return Err(Error::new_spanned(name, "invalid argument"));
+            }
+        }
+        if let Some(storage) = opts
+            .storage_setting
+            .as_ref()
+            .filter(|_| opts.intern && opts.storage != Storage::Arc)
+        {
+            return Err(Error::new_spanned(
+                storage,
+                "`intern` can only be used with `storage = Arc`",
+            ));
+        }
+        // Both only concern the variant for unknown strings, which rejecting them leaves out.
+        if let Some(reject) = reject.filter(|_| opts.reject_unknown) {
+            if opts.storage_setting.is_some() || opts.intern {
+                return Err(Error::new_spanned(
+                    reject,
+                    "`storage` and `intern` cannot be used with `unknown = \"reject\"`",
+                ));
+            }
+        }
+        Ok(opts)
+    }
+}
+
+impl Parse for SmollerOpts {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // let attrs = input.call(Attribute::parse_outer)?;
+        // dbg!(&attrs);
+        // assert_eq!(attrs.len(), 1);
+        // let attr = &attrs[0];
+        // assert!(attr.path.is_ident("smoller_str"));
+        Self::from_settings(parse_settings(input)?)
+    }
+}
+
+impl Default for SmollerOpts {
+    fn default() -> Self {
+        Self {
+            impl_deref: true,
+            no_derives: false,
+            intern: false,
+            storage: Storage::default(),
+            storage_setting: None,
+            reject_unknown: false,
+            ident_case: IdentCase::default(),
+            enum_str: EnumStrOpts::default(),
+        }
+    }
+}
+
+/// A string given to `smoller_strings!`, optionally with an explicit variant name.
+#[derive(Debug, Clone)]
+pub struct SmolItem {
+    pub value: LitStr,
+    pub ident: Option<Ident>,
+    pub aliases: Vec<LitStr>,
+    /// Lines of documentation for the variant.
+    pub docs: Vec<LitStr>,
+}
+
+impl SmolItem {
+    pub fn new(value: LitStr, ident: Option<Ident>) -> Self {
+        Self {
+            value,
+            ident,
+            aliases: Vec::new(),
+            docs: Vec::new(),
+        }
+    }
+}
+
+impl Parse for SmolItem {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let mut docs = Vec::with_capacity(attrs.len());
+        for attr in attrs {
+            match attr.parse_meta()? {
+                Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(doc),
+                    ..
+                }) if path.is_ident("doc") => docs.push(doc),
+                meta => return Err(Error::new_spanned(meta, "only doc comments are allowed")),
+            }
+        }
+        let value: LitStr = input.parse()?;
+        let ident = if input.peek(Token![=]) {
+            let _: Token![=] = input.parse()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            value,
+            ident,
+            aliases: Vec::new(),
+            docs,
+        })
+    }
+}
+
+/// An entry given to `smoller_strings!`: a string, or a `..Type` whose values it includes.
+#[derive(Debug, Clone)]
+pub enum SmolEntry {
+    String(SmolItem),
+    Nested(Path),
+}
+
+/// The entries given to `smoller_strings!`, in order.
+#[derive(Debug)]
+pub struct SmolItems {
+    pub entries: Vec<SmolEntry>,
+}
+
+impl Parse for SmolItems {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut items = Self {
+            entries: Vec::new(),
+        };
+        while !input.is_empty() {
+            if input.peek(Token![..]) {
+                let _: Token![..] = input.parse()?;
+                items.entries.push(SmolEntry::Nested(input.parse()?));
+            } else {
+                items.entries.push(SmolEntry::String(input.parse()?));
+            }
+            if input.is_empty() {
+                break;
+            }
+            let _: Token![,] = input.parse()?;
+        }
+        Ok(items)
+    }
+}
+
+pub fn smoller_func(
+    opts: SmollerOpts,
+    name: Ident,
+    entries: Vec<SmolEntry>,
+) -> Result<TokenStream> {
+    check_values(
+        entries
+            .iter()
+            .filter_map(|entry| match entry {
+                SmolEntry::String(item) => Some(item),
+                SmolEntry::Nested(_) => None,
+            })
+            .flat_map(|item| std::iter::once(&item.value).chain(&item.aliases)),
+    )?;
+
+    // Generated names have the span of their value, so errors point at it.
+    let mut idents = HashMap::<String, (Ident, String)>::new();
+    let mut errors = None;
+    let mut variants = Vec::with_capacity(entries.len());
+    let repr_ident = format_ident!("{}Repr", name);
+    let mut conversions = Vec::new();
+    for entry in &entries {
+        let (ident, name_str) = match entry {
+            SmolEntry::String(item) => match &item.ident {
+                Some(ident) => (ident.clone(), item.value.value()),
+                None => match ident::variant_ident(&item.value, opts.ident_case) {
+                    Ok(ident) => (ident, item.value.value()),
+                    Err(err) => {
+                        push_error(&mut errors, err);
+                        continue;
+                    }
+                },
+            },
+            // A `..Type` becomes a `#[nested]` variant named after the type.
+            SmolEntry::Nested(path) => (
+                path.segments.last().unwrap().ident.clone(),
+                format!("..{}", path.to_token_stream()).replace(' ', ""),
+            ),
+        };
+        if let Some((first, first_value)) = idents.get(&ident.to_string()) {
+            let msg =
+                format!("`{first_value}` and `{name_str}` both have the variant name `{ident}`");
+            let err = match entry {
+                SmolEntry::String(_) => duplicate_error(&ident, first, msg),
+                SmolEntry::Nested(path) => duplicate_error(path, first, msg),
+            };
+            push_error(&mut errors, err);
+            continue;
+        }
+        idents.insert(ident.to_string(), (ident.clone(), name_str));
+
+        let path = match entry {
+            SmolEntry::String(item) => {
+                let lit = &item.value;
+                let docs = &item.docs;
+                let aliases = &item.aliases;
+                variants.push(quote! {
+                    #(#[doc = #docs])* #[value(#lit #(, alias = #aliases)*)] #ident
+                });
+                continue;
+            }
+            SmolEntry::Nested(path) => path,
+        };
+        // The wrapper of a `#[smoller_str]` type stands for its `Repr`. The span makes a
+        // collision point at the `..Type` it comes from.
+        let ty = quote_spanned!(path.span()=> <#path as smoller_str::nested::Values>::EnumStr);
+        variants.push(quote!(#[nested] #ident(#ty)));
+        conversions.push(quote! {
+            impl From<#ty> for #repr_ident {
+                fn from(value: #ty) -> Self {
+                    Self::#ident(value)
+                }
+            }
+
+            impl From<#ty> for #name {
+                fn from(value: #ty) -> Self {
+                    Self::Builtin(#repr_ident::#ident(value))
+                }
+            }
+
+            impl TryFrom<#repr_ident> for #ty {
+                type Error = smoller_str::UnknownValue<'static, #ty>;
+
+                fn try_from(value: #repr_ident) -> Result<Self, Self::Error> {
+                    match value {
+                        #repr_ident::#ident(value) => Ok(value),
+                        value => Err(smoller_str::UnknownValue::new(value.as_str())),
+                    }
+                }
+            }
+
+            impl TryFrom<#name> for #ty {
+                type Error = smoller_str::UnknownValue<'static, #ty>;
+
+                fn try_from(value: #name) -> Result<Self, Self::Error> {
+                    match value {
+                        #name::Builtin(#repr_ident::#ident(value)) => Ok(value),
+                        value => Err(smoller_str::UnknownValue::new(
+                            smoller_str::SmollerStr::as_str(&value),
+                        )
+                        .into_owned()),
+                    }
+                }
+            }
+        });
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    // let opts = SmollerOpts::default();
+
+    // let enum_item_toks = quote! {
+    //     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumStr)]
+    //     pub enum #name {
+    //         #( #variants ),*
+    //     }
+    // }
+    // .into();
+
+    // let enum_item = parse_macro_input!(enum_item_toks as DeriveInput);
+    let allow_case =
+        (opts.ident_case != IdentCase::UpperCamel).then(|| quote!(#[allow(non_camel_case_types)]));
+    let enum_item = parse_quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumStr)]
+        #allow_case
+        pub enum #name {
+            #( #variants ),*
+        }
+    };
+
+    let mut tokens = smoller(opts, enum_item)?;
+    tokens.extend(conversions);
+    Ok(tokens)
+}
+
+pub fn smoller(opts: SmollerOpts, mut item: DeriveInput) -> Result<TokenStream> {
+    let mut derive = None;
+    item.attrs.retain(|attr| {
+        if attr.path.is_ident("derive") {
+            derive = Some(attr.clone());
+            false
+        } else {
+            true
+        }
+    });
+
+    let enum_ident = item.ident.clone();
+    let repr_ident = format_ident!("{}Repr", enum_ident);
+    item.ident = repr_ident.clone();
+
+    let vis = item.vis.clone();
+    item.vis = Visibility::Inherited;
+
+    let mut enum_str_opts = opts.enum_str.clone();
+    enum_str_opts.apply_attrs(&item.attrs)?;
+    let case_insensitive = enum_str_opts.case_insensitive;
+
+    let data = match &mut item.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                item,
+                "`smol_str` can only be used on enums",
+            ))
+        }
+    };
+
+    // The variants holding strings that are not builtin values, which the user can declare:
+    // classified ones with a rule, tried in order, and at most one catch-all without a rule.
+    let mut fallback = None::<Variant>;
+    let mut classified = Vec::<(Variant, FallbackRule)>::new();
+    let mut errors = None;
+    for variant in std::mem::take(&mut data.variants) {
+        let Some(attr) = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("fallback"))
+        else {
+            data.variants.push(variant);
+            continue;
+        };
+        let rule = match FallbackRule::parse(attr) {
+            Ok(rule) => rule,
+            Err(err) => {
+                push_error(&mut errors, err);
+                continue;
+            }
+        };
+        let single_field =
+            matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1);
+        let err = match (rule, &fallback) {
+            _ if !single_field => Error::new_spanned(
+                &variant,
+                "a `#[fallback]` variant must have a single unnamed field",
+            ),
+            (Some(rule), _) => {
+                classified.push((variant, rule));
+                continue;
+            }
+            (None, Some(first)) => duplicate_error(
+                &variant.ident,
+                &first.ident,
+                "only one `#[fallback]` variant without a rule is allowed".into(),
+            ),
+            (None, None) => {
+                fallback = Some(variant);
+                continue;
+            }
+        };
+        push_error(&mut errors, err);
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+    if let (Some(fallback), Some(storage)) = (&fallback, &opts.storage_setting) {
+        let mut err = Error::new_spanned(
+            storage,
+            "`storage` cannot be used with a `#[fallback]` variant",
+        );
+        err.combine(Error::new_spanned(&fallback.ident, "the fallback variant"));
+        return Err(err);
+    }
+    if let (Some(fallback), true) = (&fallback, opts.intern) {
+        return Err(Error::new_spanned(
+            &fallback.ident,
+            "`intern` cannot be used with a `#[fallback]` variant",
+        ));
+    }
+    // With `unknown = "reject"` there is no variant for unknown strings.
+    if let (Some(fallback), true) = (&fallback, opts.reject_unknown) {
+        return Err(Error::new_spanned(
+            &fallback.ident,
+            "a `#[fallback]` variant without a rule cannot be used with `unknown = \"reject\"`",
+        ));
+    }
+
+    // `#[value(prefix = "...")]` variants hold the rest of the strings starting with their
+    // prefix. They are tried after the builtin values and before the fallbacks.
+    let prefixes = parse_variants(data.variants.iter(), enum_str_opts.rename_all)?
+        .into_iter()
+        .filter_map(|variant| match variant {
+            VariantData::Prefix(ident, prefix, _) => Some((ident.clone(), prefix)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+    let mut prefixed = Vec::<(Variant, LitStr)>::new();
+    for variant in std::mem::take(&mut data.variants) {
+        match prefixes.get(&variant.ident) {
+            Some(prefix) => prefixed.push((variant, prefix.clone())),
+            None => data.variants.push(variant),
+        }
+    }
+    let mut seen = HashMap::<String, &LitStr>::new();
+    for (_, prefix) in &prefixed {
+        let key = if case_insensitive {
+            prefix.value().to_ascii_lowercase()
+        } else {
+            prefix.value()
+        };
+        if let Some(first) = seen.insert(key, prefix) {
+            let msg = format!("duplicate prefix `{}`", prefix.value());
+            return Err(duplicate_error(prefix, first, msg));
+        }
+    }
+
+    // The wrapper's own variants are `Builtin`, and `Unknown` unless a `#[fallback]` variant
+    // or `unknown = "reject"` takes its place.
+    let mut reserved = vec!["Builtin"];
+    if fallback.is_none() && !opts.reject_unknown {
+        reserved.push("Unknown");
+    }
+    let wrapper_variants = fallback
+        .iter()
+        .chain(classified.iter().map(|(variant, _)| variant))
+        .chain(prefixed.iter().map(|(variant, _)| variant));
+    for variant in wrapper_variants {
+        if reserved.iter().any(|name| variant.ident == name) {
+            let msg = format!(
+                "`{}` is the name of a variant generated by `#[smoller_str]`",
+                variant.ident
+            );
+            push_error(&mut errors, Error::new_spanned(&variant.ident, msg));
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let repr_variants = data
+        .variants
+        .iter()
+        .cloned()
+        .map(|mut variant| {
+            variant
+                .attrs
+                .retain(|attr| !attr.path.is_ident("value") && !attr.path.is_ident("nested"));
+            variant
+        })
+        .collect::<Vec<_>>();
+
+    let derive = match derive {
+        Some(d) => d,
+        None => {
+            return Err(Error::new_spanned(
+                item,
+                "`smoller_str` must derive `EnumStr`",
+            ))
+        }
+    };
+    let derives = derive
+        .parse_args_with(Punctuated::<Ident, Token![,]>::parse_separated_nonempty)?
+        .iter()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    macro_rules! has_derive {
+        ($trait:ident) => {
+            derives.iter().any(|d| d == stringify!($trait))
+        };
+    }
+
+    let repr_derives = derives.iter().filter(|&d| d != "EnumStr");
+
+    // The smallest integer that can hold every index, so that the repr is as compact as possible.
+    // A `#[nested]` variant has a field, and a fixed discriminant would keep its niches from
+    // being used, so those reprs are left to the compiler.
+    let has_fields = repr_variants.iter().any(|v| !v.fields.is_empty());
+    let repr_int = match repr_variants.len() {
+        _ if has_fields => None,
+        0 => None,
+        1..=0x100 => Some(quote!(u8)),
+        0x101..=0x1_0000 => Some(quote!(u16)),
+        _ => Some(quote!(u32)),
+    }
+    .map(|int| quote!(#[repr(#int)]));
+
+    // Lint attributes are for the variants, which end up in the repr.
+    let allows = item.attrs.iter().filter(|attr| attr.path.is_ident("allow"));
+
+    let mut tokens = quote! {
+        #[derive( #(#repr_derives),* )]
+        #(#allows)*
+        #repr_int
+        #vis enum #repr_ident {
+            #( #repr_variants ),*
+        }
+    };
+
+    tokens.extend(enumstr::derive_with_opts(&item, enum_str_opts)?);
+
+    let wrapper_derives = derives
+        .iter()
+        .filter(|&d| !(d == "EnumStr" || d == "Copy" || d == "Hash"))
+        .filter(|&d| !((opts.intern || case_insensitive) && d == "PartialEq"))
+        .collect::<Vec<_>>();
+
+    let (unknown, unknown_variant, storage) = match &fallback {
+        Some(variant) => {
+            let mut variant = variant.clone();
+            variant.attrs.retain(|attr| !attr.path.is_ident("fallback"));
+            let storage = Storage::from_type(&variant.fields.iter().next().unwrap().ty);
+            (variant.ident.clone(), variant.into_token_stream(), storage)
+        }
+        None => {
+            let storage_ty = opts.storage.ty();
+            (
+                format_ident!("Unknown"),
+                quote!(Unknown(#storage_ty)),
+                Some(opts.storage),
+            )
+        }
+    };
+    let has_unknown = !opts.reject_unknown;
+    let unknown_variant = has_unknown.then_some(unknown_variant);
+    let unknown_is_heap_allocated = has_unknown.then(|| {
+        let arm = Storage::is_heap_allocated_arm(storage, &unknown);
+        quote!(#arm,)
+    });
+    // The match arms for the unknown variant, if there is one.
+    let unknown_arm = |arm: TokenStream| has_unknown.then(|| quote!(Self::#unknown(#arm)));
+
+    // The variants tried in turn for strings that are not builtin values: the prefixes,
+    // longest first, then the classified fallbacks in order.
+    let mut by_length = prefixed.iter().collect::<Vec<_>>();
+    by_length.sort_by_key(|(_, prefix)| std::cmp::Reverse(prefix.value().len()));
+    let (conditions, constructors): (Vec<_>, Vec<_>) = by_length
+        .iter()
+        .map(|(variant, prefix)| {
+            let ident = &variant.ident;
+            (
+                quote!(smoller_str::lookup::has_prefix(s, #prefix, #case_insensitive)),
+                quote!(Self::#ident(s.split_at(#prefix.len()).1.into())),
+            )
+        })
+        .chain(classified.iter().map(|(variant, rule)| {
+            let ident = &variant.ident;
+            (
+                rule.condition(case_insensitive),
+                quote!(Self::#ident(s.into())),
+            )
+        }))
+        .unzip();
+
+    let other_variants = prefixed
+        .iter()
+        .map(|(variant, _)| variant)
+        .chain(classified.iter().map(|(variant, _)| variant))
+        .collect::<Vec<_>>();
+    let other_idents = other_variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let other_variant_defs = other_variants.iter().map(|&variant| {
+        let mut variant = variant.clone();
+        variant
+            .attrs
+            .retain(|attr| !attr.path.is_ident("value") && !attr.path.is_ident("fallback"));
+        variant
+    });
+    let other_is_heap_allocated = other_variants.iter().map(|variant| {
+        let storage = Storage::from_type(&variant.fields.iter().next().unwrap().ty);
+        Storage::is_heap_allocated_arm(storage, &variant.ident)
+    });
+    let prefix_idents = prefixed
+        .iter()
+        .map(|(variant, _)| &variant.ident)
+        .collect::<Vec<_>>();
+    let prefix_lits = prefixed
+        .iter()
+        .map(|(_, prefix)| prefix)
+        .collect::<Vec<_>>();
+    let classified_idents = classified
+        .iter()
+        .map(|(variant, _)| &variant.ident)
+        .collect::<Vec<_>>();
+    let field_str = |variant: &Variant| {
+        Storage::as_str(Storage::from_type(
+            &variant.fields.iter().next().unwrap().ty,
+        ))
+    };
+    let prefix_strs = prefixed
+        .iter()
+        .map(|(variant, _)| field_str(variant))
+        .collect::<Vec<_>>();
+    let classified_strs = classified
+        .iter()
+        .map(|(variant, _)| field_str(variant))
+        .collect::<Vec<_>>();
+    let unknown_str = Storage::as_str(storage);
+
+    // Prefix variants only hold the end of their string, so the full string can't be borrowed
+    // and the type gets inherent methods instead of implementing `SmollerStr`. Everything that
+    // only needs to look at the string goes through `split_prefix`.
+    let is_prefixed = !prefixed.is_empty();
+    let (method_vis, smoller_str_path) = if is_prefixed {
+        (Some(&vis), quote!(Self))
+    } else {
+        (None, quote!(smoller_str::SmollerStr))
+    };
+    let split_prefix = is_prefixed.then(|| {
+        let split_prefix_unknown =
+            unknown_arm(quote!(s)).map(|arm| quote!(#arm => ("", #unknown_str),));
+        quote! {
+            impl #enum_ident {
+                /// The prefix of a `#[value(prefix = "...")]` variant, or `""` for the other
+                /// variants, and the rest of the string.
+                #vis fn split_prefix(&self) -> (&'static str, &str) {
+                    match self {
+                        Self::Builtin(s) => ("", s.as_str()),
+                        #( Self::#prefix_idents(s) => (#prefix_lits, #prefix_strs), )*
+                        #( Self::#classified_idents(s) => ("", #classified_strs), )*
+                        #split_prefix_unknown
+                    }
+                }
+            }
+        }
+    });
+
+    // Strings matching a rule are known values too, which `unknown = "reject"` accepts.
+    let try_new = (!other_variants.is_empty()).then(|| {
+        quote! {
+            #method_vis fn try_new<S: AsRef<str> + ?Sized>(
+                s: &S,
+            ) -> Result<Self, smoller_str::UnknownValue<'_, #repr_ident>> {
+                let s = s.as_ref();
+                match #repr_ident::new(s) {
+                    Some(value) => Ok(Self::Builtin(value)),
+                    #( None if #conditions => Ok(#constructors), )*
+                    None => Err(smoller_str::UnknownValue::new(s)),
+                }
+            }
+        }
+    });
+
+    let kind = (!other_variants.is_empty()).then(|| {
+        let kind_ident = format_ident!("{}Kind", enum_ident);
+        let doc = format!("Which variant of [`{enum_ident}`] a value is in.");
+        let kind_unknown = has_unknown.then(|| quote!(#unknown,));
+        let kind_unknown_arm =
+            unknown_arm(quote!(_)).map(|arm| quote!(#arm => #kind_ident::#unknown,));
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #vis enum #kind_ident {
+                Builtin,
+                #( #other_idents, )*
+                #kind_unknown
+            }
+
+            impl #enum_ident {
+                /// Whether this is a builtin value, or which other variant holds it.
+                #vis const fn kind(&self) -> #kind_ident {
+                    match self {
+                        Self::Builtin(_) => #kind_ident::Builtin,
+                        #( Self::#other_idents(_) => #kind_ident::#other_idents, )*
+                        #kind_unknown_arm
+                    }
+                }
+            }
+        }
+    });
+
+    let (interner, unknown_from_str) = if opts.intern {
+        let interner = quote! {
+            impl #enum_ident {
+                /// The pool shared by the `Unknown` values of this type.
+                #vis fn interner() -> &'static smoller_str::Interner {
+                    static INTERNER: smoller_str::Interner = smoller_str::Interner::new();
+                    &INTERNER
+                }
+            }
+        };
+        (Some(interner), quote!(Self::interner().intern(s)))
+    } else {
+        (None, quote!(s.into()))
+    };
+
+    // Interned values are compared by pointer first, which settles most comparisons without
+    // looking at the strings. Case-insensitive values can hold any case, so everything but two
+    // builtin values is compared as strings.
+    let impl_partial_eq = if !has_derive!(PartialEq) {
+        None
+    } else if case_insensitive {
+        let rest_eq = if is_prefixed {
+            quote! {
+                _ => smoller_str::lookup::eq_split(self.split_prefix(), other.split_prefix(), true),
+            }
+        } else {
+            quote! {
+                _ => <#enum_ident as PartialEq<str>>::eq(self, smoller_str::SmollerStr::as_str(other)),
+            }
+        };
+        let ptr_eq = opts.intern.then(|| {
+            quote! {
+                (Self::#unknown(a), Self::#unknown(b)) if std::sync::Arc::ptr_eq(a, b) => true,
+            }
+        });
+        Some(quote! {
+            impl PartialEq for #enum_ident {
+                fn eq(&self, other: &Self) -> bool {
+                    match (self, other) {
+                        (Self::Builtin(a), Self::Builtin(b)) => a == b,
+                        #ptr_eq
+                        #rest_eq
+                    }
+                }
+            }
+        })
+    } else {
+        opts.intern.then(|| {
+            quote! {
+                impl PartialEq for #enum_ident {
+                    fn eq(&self, other: &Self) -> bool {
+                        match (self, other) {
+                            (Self::Builtin(a), Self::Builtin(b)) => a == b,
+                            (Self::#unknown(a), Self::#unknown(b)) => {
+                                std::sync::Arc::ptr_eq(a, b) || a == b
+                            }
+                            #( (Self::#other_idents(a), Self::#other_idents(b)) => a == b, )*
+                            _ => false,
+                        }
+                    }
+                }
+            }
+        })
+    };
+
+    let impl_hash = has_derive!(Hash).then(|| {
+        let hash = if is_prefixed {
+            quote! {
+                let (prefix, rest) = self.split_prefix();
+                smoller_str::lookup::hash_split(prefix, rest, #case_insensitive, hasher)
+            }
+        } else if case_insensitive {
+            quote! {
+                smoller_str::lookup::hash_ignore_ascii_case(smoller_str::SmollerStr::as_str(self), hasher)
+            }
+        } else {
+            quote! {
+                smoller_str::SmollerStr::as_str(self).hash(hasher)
+            }
+        };
+        quote! {
+            impl std::hash::Hash for #enum_ident {
+                fn hash<H: std::hash::Hasher>(&self, hasher: &mut H) {
+                    #hash
+                }
+            }
+        }
+    });
+
+    let (eq_str, eq_str_ref) = if is_prefixed {
+        let eq_str = quote! {
+            let (prefix, rest) = self.split_prefix();
+            smoller_str::lookup::eq_prefixed(other, prefix, rest, #case_insensitive)
+        };
+        (
+            eq_str,
+            quote!(<#enum_ident as PartialEq<str>>::eq(self, other)),
+        )
+    } else {
+        let eq_str = if case_insensitive {
+            quote!(<#enum_ident as smoller_str::SmollerStr>::as_str(self).eq_ignore_ascii_case(other))
+        } else {
+            quote!(<#enum_ident as smoller_str::SmollerStr>::as_str(self) == other)
+        };
+        (eq_str.clone(), eq_str)
+    };
+
+    let display = if is_prefixed {
+        quote! {
+            let (prefix, rest) = self.split_prefix();
+            f.write_str(prefix)?;
+            f.write_str(rest)
+        }
+    } else {
+        quote!(f.write_str(smoller_str::SmollerStr::as_str(self)))
+    };
+
+    // if !has_derive!(Eq) {
+    //     return Err(Error::new_spanned(
+    //         derive,
+    //         "`smoller_str` must derive `PartialEq`",
+    //     ));
+    // }
+
+    let deref = (opts.impl_deref && !is_prefixed).then(|| {
+        quote! {
+            impl std::ops::Deref for #enum_ident {
+                type Target = str;
+
+                fn deref(&self) -> &str {
+                    smoller_str::SmollerStr::as_str(self)
+                }
+            }
+        }
+    });
+
+    let borrow = (!is_prefixed).then(|| {
+        // Eq and Hash ignore case, so borrowing as a case-sensitive `str`
+        // would break the `Borrow` contract.
+        let impl_borrow = (!case_insensitive).then(|| {
+            quote! {
+                impl std::borrow::Borrow<str> for #enum_ident {
+                    fn borrow(&self) -> &str {
+                        smoller_str::SmollerStr::as_str(self)
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #impl_borrow
+
+            impl AsRef<str> for #enum_ident {
+                fn as_ref(&self) -> &str {
+                    smoller_str::SmollerStr::as_str(self)
+                }
+            }
+        }
+    });
+
+    let serialize = if is_prefixed {
+        quote!(serializer.collect_str(self))
+    } else {
+        quote!(smoller_str::serde::serialize(
+            smoller_str::SmollerStr::as_str(self),
+            serializer
+        ))
+    };
+    let deserialize = if opts.reject_unknown {
+        quote!(smoller_str::serde::deserialize_known_smoller_str(
+            deserializer
+        ))
+    } else {
+        quote!(smoller_str::serde::deserialize_smoller_str(deserializer))
+    };
+    let impl_serde = opts.enum_str.serde.then(|| {
+        quote! {
+            impl smoller_str::serde::Serialize for #enum_ident {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: smoller_str::serde::Serializer,
+                {
+                    #serialize
+                }
+            }
+
+            impl<'de> smoller_str::serde::Deserialize<'de> for #enum_ident {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: smoller_str::serde::Deserializer<'de>,
+                {
+                    #deserialize
+                }
+            }
+        }
+    });
+
+    let from_str = if opts.reject_unknown {
+        quote! {
+            impl<'a> TryFrom<&'a str> for #enum_ident {
+                type Error = smoller_str::UnknownValue<'a, #repr_ident>;
+
+                fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+                    #smoller_str_path::try_new(value)
+                }
+            }
+
+            impl std::str::FromStr for #enum_ident {
+                type Err = smoller_str::UnknownValue<'static, #repr_ident>;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    #smoller_str_path::try_new(s).map_err(smoller_str::UnknownValue::into_owned)
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl From<&str> for #enum_ident {
+                fn from(value: &str) -> Self {
+                    #smoller_str_path::new(value)
+                }
+            }
+
+            impl std::str::FromStr for #enum_ident {
+                type Err = std::convert::Infallible;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    Ok(#smoller_str_path::new(s))
+                }
+            }
+        }
+    };
+
+    let new_unknown = match unknown_arm(unknown_from_str) {
+        Some(unknown) => unknown,
+        None => quote!(panic!("{}", smoller_str::UnknownValue::<#repr_ident>::new(s))),
+    };
+    let unknown_arm = |arm: TokenStream, value: TokenStream| {
+        unknown_arm(arm).map(|unknown| quote!(#unknown => #value,))
+    };
+    let builtin_unknown = unknown_arm(quote!(_), quote!(None));
+    let as_str_unknown = unknown_arm(quote!(s), unknown_str.clone());
+    let as_cow_unknown = unknown_arm(quote!(s), quote!(std::borrow::Cow::Borrowed(#unknown_str)));
+    let methods = quote! {
+        #method_vis fn new<S: AsRef<str> + ?Sized>(s: &S) -> Self {
+            let s = s.as_ref();
+            match #repr_ident::new(s) {
+                Some(value) => Self::Builtin(value),
+                #( None if #conditions => #constructors, )*
+                None => #new_unknown,
+            }
+        }
+    };
+    let more_methods = quote! {
+        #method_vis fn is_heap_allocated(&self) -> bool {
+            match self {
+                Self::Builtin(_) => false,
+                #( #other_is_heap_allocated, )*
+                #unknown_is_heap_allocated
+            }
+        }
+
+        #method_vis fn builtin(&self) -> Option<#repr_ident> {
+            match self {
+                Self::Builtin(s) => Some(*s),
+                #( Self::#other_idents(_) => None, )*
+                #builtin_unknown
+            }
+        }
+
+        #method_vis fn is_builtin_value(&self) -> bool {
+            matches!(self, Self::Builtin(_))
+        }
+
+        #try_new
+    };
+    let impl_smoller_str = if is_prefixed {
+        quote! {
+            impl #enum_ident {
+                #methods
+
+                /// The full string, which is only allocated for the prefix variants.
+                #vis fn as_str(&self) -> std::borrow::Cow<'_, str> {
+                    match self {
+                        Self::Builtin(s) => std::borrow::Cow::Borrowed(s.as_str()),
+                        #(
+                            Self::#prefix_idents(s) => std::borrow::Cow::Owned(
+                                [#prefix_lits, #prefix_strs].concat(),
+                            ),
+                        )*
+                        #(
+                            Self::#classified_idents(s) => std::borrow::Cow::Borrowed(#classified_strs),
+                        )*
+                        #as_cow_unknown
+                    }
+                }
+
+                #more_methods
+            }
+
+            impl smoller_str::collections::SmollerKey for #enum_ident {
+                type Builtin = #repr_ident;
+
+                fn from_builtin(value: #repr_ident) -> Self {
+                    Self::Builtin(value)
+                }
+
+                fn builtin(&self) -> Option<#repr_ident> {
+                    self.builtin()
+                }
+
+                fn to_str(&self) -> std::borrow::Cow<'_, str> {
+                    self.as_str()
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl smoller_str::SmollerStr for #enum_ident {
+                type Builtin = #repr_ident;
+
+                #methods
+
+                fn from_builtin(value: #repr_ident) -> Self {
+                    Self::Builtin(value)
+                }
+
+                fn as_str(&self) -> &str {
+                    match self {
+                        Self::Builtin(s) => s.as_str(),
+                        #( Self::#classified_idents(s) => #classified_strs, )*
+                        #as_str_unknown
+                    }
+                }
+
+                #more_methods
+            }
+        }
+    };
+
+    let literal_values = repr_variants.iter().map(|v| {
+        let ident = &v.ident;
+        quote! { Self::Builtin(#repr_ident::#ident) }
+    });
+    // The values of `#[nested]` variants are only known once the nested types are compiled.
+    let builtin = if repr_variants.iter().all(|v| v.fields.is_empty()) {
+        quote!(&[ #( #literal_values ),* ])
+    } else {
+        quote! {
+            &{
+                const COUNT: usize = <#repr_ident as smoller_str::EnumStr>::COUNT;
+                const FIRST: #enum_ident =
+                    #enum_ident::Builtin(<#repr_ident as smoller_str::EnumStr>::VALUES[0]);
+                let values = <#repr_ident as smoller_str::EnumStr>::VALUES;
+                let mut builtin = [FIRST; COUNT];
+                let mut i = 0;
+                while i < COUNT {
+                    // `FIRST` holds no string, but the wrapper can't be dropped in a `const`.
+                    let first = std::mem::replace(&mut builtin[i], Self::Builtin(values[i]));
+                    std::mem::forget(first);
+                    i += 1;
+                }
+                builtin
+            }
+        }
+    };
+
+    tokens.extend(quote! {
+        #[derive( #(#wrapper_derives),* )]
+        #vis enum #enum_ident {
+            Builtin(#repr_ident),
+            #( #other_variant_defs, )*
+            #unknown_variant
+        }
+
+        impl #enum_ident {
+            #vis const BUILTIN: &'static [Self] = #builtin;
+
+            /// Wraps a builtin value, in a `const` context.
+            #vis const fn from_builtin(value: #repr_ident) -> Self {
+                Self::Builtin(value)
+            }
+
+            /// The wrapper for `s` if it is a builtin value, in a `const` context.
+            #vis const fn try_builtin(s: &str) -> Option<Self> {
+                match #repr_ident::new(s) {
+                    Some(value) => Some(Self::Builtin(value)),
+                    None => None,
+                }
+            }
+        }
+
+        #impl_smoller_str
+
+        impl smoller_str::nested::Values for #enum_ident {
+            type EnumStr = #repr_ident;
+        }
+
+        #split_prefix
+
+        impl From<#repr_ident> for #enum_ident {
+            fn from(value: #repr_ident) -> Self {
+                Self::Builtin(value)
+            }
+        }
+
+        #from_str
+
+        #kind
+
+        #interner
+
+        #impl_partial_eq
+
+        #impl_hash
+
+        impl PartialEq<str> for #enum_ident {
+            fn eq(&self, other: &str) -> bool {
+                #eq_str
+            }
+        }
+
+        impl PartialEq<str> for &'_ #enum_ident {
+            fn eq(&self, other: &str) -> bool {
+                #eq_str_ref
+            }
+        }
+
+        impl std::fmt::Display for #enum_ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                #display
+            }
+        }
+
+        #deref
+
+        #impl_serde
+
+        #borrow
+    });
+
+    // macro_rules! check_derive {
+    //     ($($trait:ident),+ $(,)?) => {{
+    //         $(
+    //             if !derives.iter().any(|d| d == stringify!($trait)) {
+    //                 return Err(Error::new_spanned(derives, concat!("`smoller_str` must derive `", stringify!($trait), "`")))
+    //             }
+    //         )+
+    //     }}
+    // }
+    // check_derive!(EnumStr, Clone, Copy, PartialEq, Eq);
+
+    // if !derives.iter().any(|d| d == "EnumStr") {
+    //     return Err(Error::new_spanned(derives, "`smoller_str` must derive `EnumStr`"))
+    // }
+    Ok(tokens)
+}
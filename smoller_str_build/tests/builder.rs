@@ -0,0 +1,76 @@
+use expect_test::expect_file;
+use smoller_str::SmollerStr;
+use smoller_str_build::Builder;
+
+fn builder() -> Builder {
+    Builder::new("Methods")
+        .values(["GET", "POST"])
+        .named_value("Content Type", "ContentType")
+        .unwrap()
+        .file("tests/data/methods.txt")
+        .unwrap()
+        .setting(r#"lookup = "phf""#)
+        .unwrap()
+}
+
+#[test]
+fn generated_code() {
+    let code = builder().generate().unwrap();
+    expect_file!["generated/methods.rs"].assert_eq(&code);
+}
+
+#[test]
+fn serde_setting() {
+    let serialize = "impl smoller_str::serde::Serialize for Methods";
+    assert!(!builder().generate().unwrap().contains(serialize));
+    let code = builder().setting("serde").unwrap().generate().unwrap();
+    assert!(code.contains(serialize));
+    assert!(code.contains("impl smoller_str::serde::Serialize for MethodsRepr"));
+}
+
+#[test]
+fn search_setting() {
+    let searcher = "fn searcher()";
+    assert!(!builder().generate().unwrap().contains(searcher));
+    let code = builder().setting("search").unwrap().generate().unwrap();
+    assert!(code.contains(searcher));
+}
+
+#[test]
+fn generation_is_deterministic() {
+    assert_eq!(builder().generate().unwrap(), builder().generate().unwrap());
+}
+
+#[test]
+fn invalid_input() {
+    assert!(Builder::new("not an ident").generate().is_err());
+    assert!(Builder::new("Methods").setting("lookup = ").is_err());
+    assert!(Builder::new("Methods")
+        .setting("no_such_setting")
+        .unwrap()
+        .generate()
+        .is_err());
+    assert!(Builder::new("Methods")
+        .file("tests/data/missing.txt")
+        .is_err());
+}
+
+mod generated {
+    include!("generated/methods.rs");
+}
+
+#[test]
+fn generated_type() {
+    use generated::{Methods, MethodsRepr};
+
+    assert_eq!(Methods::new("GET").builtin(), Some(MethodsRepr::Get));
+    assert_eq!(
+        Methods::new("M-SEARCH").builtin(),
+        Some(MethodsRepr::MSearch)
+    );
+    assert_eq!(
+        Methods::new("Content Type").builtin(),
+        Some(MethodsRepr::ContentType)
+    );
+    assert!(!Methods::new("PUT").is_builtin_value());
+}
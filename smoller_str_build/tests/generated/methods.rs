@@ -0,0 +1,316 @@
+// @generated by smoller_str_build, do not edit.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum MethodsRepr {
+    Get,
+    Post,
+    ContentType,
+    MSearch,
+    Notify,
+}
+impl MethodsRepr {
+    const fn new(s: &str) -> Option<Self> {
+        {
+            const DISPLACEMENTS: [(u32, u32); 1usize] = [(2u32, 0u32)];
+            const SLOTS: [(&[u8], MethodsRepr); 5usize] = [
+                (b"Content Type", MethodsRepr::ContentType),
+                (b"POST", MethodsRepr::Post),
+                (b"M-SEARCH", MethodsRepr::MSearch),
+                (b"NOTIFY", MethodsRepr::Notify),
+                (b"GET", MethodsRepr::Get),
+            ];
+            let bytes = s.as_bytes();
+            let (key, value) = SLOTS[smoller_str::lookup::slot(
+                bytes,
+                8u64,
+                false,
+                &DISPLACEMENTS,
+                5usize,
+            )];
+            if smoller_str::lookup::bytes_eq(key, bytes) { Some(value) } else { None }
+        }
+    }
+    const fn as_str(&self) -> &'static str {
+        match self {
+            MethodsRepr::Get => "GET",
+            MethodsRepr::Post => "POST",
+            MethodsRepr::ContentType => "Content Type",
+            MethodsRepr::MSearch => "M-SEARCH",
+            MethodsRepr::Notify => "NOTIFY",
+        }
+    }
+    /// The longest value (or alias) that `s` starts with, and the rest of `s`.
+    const fn parse_prefix(s: &str) -> Option<(Self, &str)> {
+        match MethodsRepr::parse_prefix_bytes(s.as_bytes()) {
+            Some((value, rest)) => Some((value, s.split_at(s.len() - rest.len()).1)),
+            None => None,
+        }
+    }
+    /// The longest value (or alias) that `bytes` starts with, and the rest of `bytes`.
+    const fn parse_prefix_bytes(bytes: &[u8]) -> Option<(Self, &[u8])> {
+        {
+            const KEYS: &[(&str, MethodsRepr)] = &[
+                ("GET", MethodsRepr::Get),
+                ("POST", MethodsRepr::Post),
+                ("Content Type", MethodsRepr::ContentType),
+                ("M-SEARCH", MethodsRepr::MSearch),
+                ("NOTIFY", MethodsRepr::Notify),
+            ];
+            let mut best: Option<(MethodsRepr, usize)> = None;
+            let mut i = 0;
+            while i < KEYS.len() {
+                let (key, value) = KEYS[i];
+                if smoller_str::lookup::has_prefix_bytes(bytes, key, false) {
+                    match best {
+                        Some((_, len)) if len >= key.len() => {}
+                        _ => best = Some((value, key.len())),
+                    }
+                }
+                i += 1;
+            }
+            match best {
+                Some((value, len)) => Some((value, bytes.split_at(len).1)),
+                None => None,
+            }
+        }
+    }
+    /// The position of this value in declaration order.
+    const fn index(&self) -> usize {
+        match self {
+            MethodsRepr::Get => 0usize,
+            MethodsRepr::Post => 1usize,
+            MethodsRepr::ContentType => 2usize,
+            MethodsRepr::MSearch => 3usize,
+            MethodsRepr::Notify => 4usize,
+        }
+    }
+    /// The value at `index` in declaration order.
+    const fn from_index(index: usize) -> Option<Self> {
+        match index {
+            0usize => Some(MethodsRepr::Get),
+            1usize => Some(MethodsRepr::Post),
+            2usize => Some(MethodsRepr::ContentType),
+            3usize => Some(MethodsRepr::MSearch),
+            4usize => Some(MethodsRepr::Notify),
+            _ => None,
+        }
+    }
+    /// The other spellings that parse into this value.
+    const fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            MethodsRepr::Get => &[],
+            MethodsRepr::Post => &[],
+            MethodsRepr::ContentType => &[],
+            MethodsRepr::MSearch => &[],
+            MethodsRepr::Notify => &[],
+        }
+    }
+}
+/// What the `#[nested]` variants of other types call, since those can be in a module
+/// where the functions above are private.
+#[doc(hidden)]
+impl MethodsRepr {
+    pub const fn __nested_new(s: &str) -> Option<Self> {
+        MethodsRepr::new(s)
+    }
+    pub const fn __nested_as_str(&self) -> &'static str {
+        MethodsRepr::as_str(self)
+    }
+    pub const fn __nested_parse_prefix_bytes(bytes: &[u8]) -> Option<(Self, &[u8])> {
+        MethodsRepr::parse_prefix_bytes(bytes)
+    }
+    pub const fn __nested_index(&self) -> usize {
+        MethodsRepr::index(self)
+    }
+    pub const fn __nested_from_index(index: usize) -> Option<Self> {
+        MethodsRepr::from_index(index)
+    }
+    pub const fn __nested_aliases(&self) -> &'static [&'static str] {
+        MethodsRepr::aliases(self)
+    }
+}
+impl smoller_str::CaseSensitive for MethodsRepr {}
+impl smoller_str::nested::Values for MethodsRepr {
+    type EnumStr = Self;
+}
+impl smoller_str::EnumStr for MethodsRepr {
+    const VALUES: &'static [Self] = &[
+        Self::Get,
+        Self::Post,
+        Self::ContentType,
+        Self::MSearch,
+        Self::Notify,
+    ];
+    const ALIASES: &'static [(&'static str, Self)] = &[];
+    const CASE_INSENSITIVE: bool = false;
+    const COUNT: usize = 5usize;
+    fn as_str(&self) -> &'static str {
+        MethodsRepr::as_str(self)
+    }
+    fn aliases(&self) -> &'static [&'static str] {
+        MethodsRepr::aliases(self)
+    }
+    fn index(&self) -> usize {
+        MethodsRepr::index(self)
+    }
+    fn from_index(index: usize) -> Option<Self> {
+        MethodsRepr::from_index(index)
+    }
+    fn parse_prefix(s: &str) -> Option<(Self, &str)> {
+        MethodsRepr::parse_prefix(s)
+    }
+    fn parse_prefix_bytes(bytes: &[u8]) -> Option<(Self, &[u8])> {
+        MethodsRepr::parse_prefix_bytes(bytes)
+    }
+}
+impl std::fmt::Display for MethodsRepr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(MethodsRepr::as_str(self))
+    }
+}
+impl std::str::FromStr for MethodsRepr {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MethodsRepr::new(s).ok_or(())
+    }
+}
+impl AsRef<str> for MethodsRepr {
+    fn as_ref(&self) -> &str {
+        MethodsRepr::as_str(self)
+    }
+}
+impl PartialEq<str> for MethodsRepr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+impl PartialEq<&'_ str> for MethodsRepr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+impl PartialEq<str> for &'_ MethodsRepr {
+    fn eq(&self, other: &str) -> bool {
+        MethodsRepr::as_str(self) == other
+    }
+}
+impl PartialEq<MethodsRepr> for str {
+    fn eq(&self, other: &MethodsRepr) -> bool {
+        self == other.as_str()
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Methods {
+    Builtin(MethodsRepr),
+    Unknown(std::sync::Arc<str>),
+}
+impl Methods {
+    pub const BUILTIN: &'static [Self] = &[
+        Self::Builtin(MethodsRepr::Get),
+        Self::Builtin(MethodsRepr::Post),
+        Self::Builtin(MethodsRepr::ContentType),
+        Self::Builtin(MethodsRepr::MSearch),
+        Self::Builtin(MethodsRepr::Notify),
+    ];
+    /// Wraps a builtin value, in a `const` context.
+    pub const fn from_builtin(value: MethodsRepr) -> Self {
+        Self::Builtin(value)
+    }
+    /// The wrapper for `s` if it is a builtin value, in a `const` context.
+    pub const fn try_builtin(s: &str) -> Option<Self> {
+        match MethodsRepr::new(s) {
+            Some(value) => Some(Self::Builtin(value)),
+            None => None,
+        }
+    }
+}
+impl smoller_str::SmollerStr for Methods {
+    type Builtin = MethodsRepr;
+    fn new<S: AsRef<str> + ?Sized>(s: &S) -> Self {
+        let s = s.as_ref();
+        match MethodsRepr::new(s) {
+            Some(value) => Self::Builtin(value),
+            None => Self::Unknown(s.into()),
+        }
+    }
+    fn from_builtin(value: MethodsRepr) -> Self {
+        Self::Builtin(value)
+    }
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Builtin(s) => s.as_str(),
+            Self::Unknown(s) => AsRef::<str>::as_ref(s),
+        }
+    }
+    fn is_heap_allocated(&self) -> bool {
+        match self {
+            Self::Builtin(_) => false,
+            Self::Unknown(_) => true,
+        }
+    }
+    fn builtin(&self) -> Option<MethodsRepr> {
+        match self {
+            Self::Builtin(s) => Some(*s),
+            Self::Unknown(_) => None,
+        }
+    }
+    fn is_builtin_value(&self) -> bool {
+        matches!(self, Self::Builtin(_))
+    }
+}
+impl smoller_str::nested::Values for Methods {
+    type EnumStr = MethodsRepr;
+}
+impl From<MethodsRepr> for Methods {
+    fn from(value: MethodsRepr) -> Self {
+        Self::Builtin(value)
+    }
+}
+impl From<&str> for Methods {
+    fn from(value: &str) -> Self {
+        smoller_str::SmollerStr::new(value)
+    }
+}
+impl std::str::FromStr for Methods {
+    type Err = std::convert::Infallible;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(smoller_str::SmollerStr::new(s))
+    }
+}
+impl std::hash::Hash for Methods {
+    fn hash<H: std::hash::Hasher>(&self, hasher: &mut H) {
+        smoller_str::SmollerStr::as_str(self).hash(hasher)
+    }
+}
+impl PartialEq<str> for Methods {
+    fn eq(&self, other: &str) -> bool {
+        <Methods as smoller_str::SmollerStr>::as_str(self) == other
+    }
+}
+impl PartialEq<str> for &'_ Methods {
+    fn eq(&self, other: &str) -> bool {
+        <Methods as smoller_str::SmollerStr>::as_str(self) == other
+    }
+}
+impl std::fmt::Display for Methods {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(smoller_str::SmollerStr::as_str(self))
+    }
+}
+impl std::ops::Deref for Methods {
+    type Target = str;
+    fn deref(&self) -> &str {
+        smoller_str::SmollerStr::as_str(self)
+    }
+}
+impl std::borrow::Borrow<str> for Methods {
+    fn borrow(&self) -> &str {
+        smoller_str::SmollerStr::as_str(self)
+    }
+}
+impl AsRef<str> for Methods {
+    fn as_ref(&self) -> &str {
+        smoller_str::SmollerStr::as_str(self)
+    }
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use proc_macro2::Span;
+use smoller_str_build::{
+    include::{self, Format},
+    smoller::{SmolEntry, SmolItem, SmolItems},
+};
+use syn::LitStr;
+
+fn docs(item: &SmolItem) -> Vec<String> {
+    item.docs.iter().map(LitStr::value).collect()
+}
+
+fn entry_docs(entry: &SmolEntry) -> Vec<String> {
+    match entry {
+        SmolEntry::String(item) => docs(item),
+        SmolEntry::Nested(_) => panic!("expected a string"),
+    }
+}
+
+#[test]
+fn documented_items() {
+    let items: SmolItems = syn::parse_str(
+        r#"
+        /// The first value.
+        "first" = First,
+        "second" = Second,
+        "#,
+    )
+    .unwrap();
+    assert_eq!(entry_docs(&items.entries[0]), [" The first value."]);
+    assert!(entry_docs(&items.entries[1]).is_empty());
+
+    let file = LitStr::new("tests/data/documented.txt", Span::call_site());
+    let items = include::read(Path::new(&file.value()), Format::Lines, &file).unwrap();
+    let values = items
+        .iter()
+        .map(|item| item.value.value())
+        .collect::<Vec<_>>();
+    assert_eq!(values, ["first", "second", "a=b"]);
+    assert!(items.iter().all(|item| item.ident.is_none()));
+    assert_eq!(docs(&items[0]), [" The first value.", " It has two lines."]);
+    assert!(docs(&items[1]).is_empty());
+    assert_eq!(docs(&items[2]), [" A value with `=` in it."]);
+}
+
+#[test]
+fn bad_entries_name_their_field() {
+    let read = |path: &str, format| {
+        let file = LitStr::new(path, Span::call_site());
+        include::read(Path::new(path), format, &file)
+            .err()
+            .unwrap()
+            .to_string()
+    };
+    let json = read("tests/data/bad_entry.json", Format::Json);
+    assert!(json.contains("unknown field `alias`"), "{json}");
+    let toml = read("tests/data/bad_entry.toml", Format::Toml);
+    assert!(toml.contains("missing field `value`"), "{toml}");
+}
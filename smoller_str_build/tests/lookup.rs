@@ -0,0 +1,26 @@
+use smoller_str_build::lookup;
+
+#[test]
+fn hash_matches_runtime() {
+    let inputs: [&[u8]; 6] = [
+        b"",
+        b"GET",
+        b"get",
+        b"Content-Type",
+        b"\xff\x00",
+        b"M-SEARCH",
+    ];
+    for bytes in inputs {
+        for seed in [0, 1, 42, u64::MAX] {
+            for ignore_case in [false, true] {
+                let build = lookup::hash(bytes, seed, ignore_case);
+                let runtime = smoller_str::lookup::hash(bytes, seed, ignore_case);
+                assert_eq!(
+                    (build.g, build.f1, build.f2),
+                    (runtime.g, runtime.f1, runtime.f2),
+                    "{bytes:?} {seed} {ignore_case}"
+                );
+            }
+        }
+    }
+}
@@ -1,17 +1,24 @@
 use proc_macro::TokenStream;
 
-use syn::{bracketed, parse::Parse, parse_macro_input, DeriveInput, Ident, LitStr, Token};
+use syn::{bracketed, parse::Parse, parse_macro_input, DeriveInput, Ident, Token};
 
-use crate::smoller::SmollerOpts;
+use smoller_str_build::{enumstr, include, shared::parse_settings, smoller, smoller::SmollerOpts};
 
-mod enumstr;
-mod shared;
-mod smoller;
+/// The options turned on by the features of `smoller_str`, which enables the matching features
+/// of this crate.
+fn default_opts() -> SmollerOpts {
+    let mut opts = SmollerOpts::default();
+    opts.enum_str.serde = cfg!(feature = "serde");
+    opts.enum_str.search = cfg!(feature = "search");
+    opts
+}
 
-#[proc_macro_derive(EnumStr, attributes(value))]
+#[proc_macro_derive(EnumStr, attributes(value, enum_str, nested))]
 pub fn derive_enumstr(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    enumstr::derive(&input)
+    let mut opts = default_opts().enum_str;
+    opts.apply_attrs(&input.attrs)
+        .and_then(|()| enumstr::derive_with_opts(&input, opts))
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
@@ -30,15 +37,10 @@ pub fn smoller_str(attr: TokenStream, item: TokenStream) -> TokenStream {
     dbg!(&item);
     let input = parse_macro_input!(item as DeriveInput);
 
-    let opts = if attr.is_empty() {
-        SmollerOpts::default()
-    } else {
-        let attrs = parse_macro_input!(attr as SmollerOpts);
-        // let args = attr
-        // SmollerOpts {
-
-        // }
-        attrs
+    let settings = parse_macro_input!(attr with parse_settings);
+    let opts = match default_opts().with_settings(settings) {
+        Ok(opts) => opts,
+        Err(e) => return e.to_compile_error().into(),
     };
 
     smoller::smoller(opts, input)
@@ -50,33 +52,43 @@ pub fn smoller_str(attr: TokenStream, item: TokenStream) -> TokenStream {
 pub fn smoller_strings(item: TokenStream) -> TokenStream {
     dbg!(&item);
 
-    #[derive(Debug)]
     struct Input {
         name: Ident,
-        comma: Token![,],
-        bracket: syn::token::Bracket,
+        _comma: Token![,],
+        _bracket: syn::token::Bracket,
         content: proc_macro2::TokenStream,
+        opts: SmollerOpts,
     }
     impl Parse for Input {
         fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
             let content;
             Ok(Input {
                 name: input.parse()?,
-                comma: input.parse()?,
-                bracket: bracketed!(content in input),
+                _comma: input.parse()?,
+                _bracket: bracketed!(content in input),
                 content: content.parse()?,
+                opts: if input.is_empty() {
+                    default_opts()
+                } else {
+                    let _: Token![,] = input.parse()?;
+                    default_opts().with_settings(parse_settings(input)?)?
+                },
             })
         }
     }
-    let Input { name, content, .. } = parse_macro_input!(item as Input);
-    let opts = SmollerOpts::default();
-
-    let strings = match syn::parse2::<smoller::SmolItems>(content) {
-        Ok(smoller::SmolItems(strings)) => strings,
+    let Input {
+        name,
+        content,
+        opts,
+        ..
+    } = parse_macro_input!(item as Input);
+
+    let items = match syn::parse2::<smoller::SmolItems>(content) {
+        Ok(items) => items,
         Err(e) => return e.to_compile_error().into(),
     };
 
-    smoller::smoller_func(opts, name, strings)
+    smoller::smoller_func(opts, name, items.entries)
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
@@ -85,44 +97,8 @@ pub fn smoller_strings(item: TokenStream) -> TokenStream {
 // pub fn include_smoller_strings_inner(item: TokenStream) -> TokenStream {
 pub fn include_smoller_strings(item: TokenStream) -> TokenStream {
     dbg!(&item);
-    struct Input {
-        name: Ident,
-        _comma: Token![,],
-        file: LitStr,
-    }
-    impl Parse for Input {
-        fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-            Ok(Input {
-                name: input.parse()?,
-                _comma: input.parse()?,
-                file: input.parse()?,
-            })
-        }
-    }
-
-    let Input { name, file, .. } = parse_macro_input!(item as Input);
-
-    // let span_file = ident.span().source_file().path();
-    // let file_name = file.value();
-    // let input_file = span_file.parent().unwrap().join(file_name);
-    let input_file = file.value();
-
-    let file_contents = match std::fs::read_to_string(&input_file) {
-        Ok(c) => c.lines().collect::<Vec<_>>().join(",\n"),
-        Err(e) => {
-            return syn::Error::new_spanned(file, format!("Unable to read {input_file:?}: {e}"))
-                .to_compile_error()
-                .into();
-        }
-    };
-
-    let strings = match syn::parse_str(&file_contents) {
-        Ok(smoller::SmolItems(strings)) => strings,
-        Err(e) => return e.to_compile_error().into(),
-    };
-
-    let opts = SmollerOpts::default();
-    smoller::smoller_func(opts, name, strings)
+    let input = parse_macro_input!(item as include::Input);
+    include::include(input, default_opts())
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
@@ -0,0 +1,48 @@
+use smoller_str::*;
+
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+#[enum_str(lookup = "phf")]
+pub enum Method {
+    #[value("GET", alias = "get", alias = "Get")]
+    Get,
+    #[value("POST")]
+    Post,
+}
+
+#[smoller_str]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Flag {
+    #[value("--verbose", alias = "-v")]
+    Verbose,
+    #[value("--quiet", alias = "-q",)]
+    Quiet,
+}
+
+#[test]
+fn aliases_parse_to_canonical() {
+    for s in ["GET", "get", "Get"] {
+        let method = Method::new(s).unwrap();
+        assert_eq!(method, Method::Get);
+        assert_eq!(method.as_str(), "GET");
+        assert_eq!(method.to_string(), "GET");
+    }
+    assert_eq!(Method::new("gEt"), None);
+}
+
+#[test]
+fn alias_table() {
+    assert_eq!(
+        <Method as EnumStr>::ALIASES,
+        &[("get", Method::Get), ("Get", Method::Get)]
+    );
+    assert_eq!(Method::Get.aliases(), &["get", "Get"]);
+    assert!(Method::Post.aliases().is_empty());
+}
+
+#[test]
+fn smoller_str_aliases() {
+    let flag = Flag::new("-v");
+    assert_eq!(flag, Flag::Builtin(FlagRepr::Verbose));
+    assert_eq!(flag.as_str(), "--verbose");
+    assert_eq!(FlagRepr::Quiet.aliases(), &["-q"]);
+}
@@ -0,0 +1,59 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use smoller_str::*;
+
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+#[enum_str(case_insensitive)]
+pub enum Keyword {
+    #[value("SELECT")]
+    Select,
+    #[value("FROM")]
+    From,
+}
+
+#[smoller_str(case_insensitive, lookup = "phf")]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Header {
+    #[value("Content-Type")]
+    ContentType,
+    #[value("Accept")]
+    Accept,
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn enum_str_ignores_case() {
+    assert_eq!(Keyword::new("select"), Some(Keyword::Select));
+    assert_eq!("FrOm".parse(), Ok(Keyword::From));
+    assert_eq!(Keyword::new("selec"), None);
+    assert!(Keyword::Select == *"Select");
+    assert_eq!(Keyword::Select.as_str(), "SELECT");
+}
+
+#[test]
+fn smoller_str_ignores_case() {
+    let header = Header::new("content-type");
+    assert_eq!(header, Header::Builtin(HeaderRepr::ContentType));
+    assert_eq!(header.as_str(), "Content-Type");
+    assert!(header == *"CONTENT-TYPE");
+    assert_eq!(hash(&header), hash(&Header::new("Content-Type")));
+    assert!(Header::new("content-length").is_heap_allocated());
+}
+
+#[test]
+fn smoller_str_map_ignores_case() {
+    let mut map = HashMap::new();
+    map.insert(Header::new("accept"), 1);
+    map.insert(Header::new("X-Request-Id"), 2);
+    assert_eq!(map.get(&Header::new("ACCEPT")), Some(&1));
+    assert_eq!(map.get(&Header::new("x-request-id")), Some(&2));
+    assert_eq!(map.get(&Header::new("x-request")), None);
+}
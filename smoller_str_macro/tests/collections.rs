@@ -0,0 +1,138 @@
+use smoller_str::{smoller_str, EnumStr, EnumStrMap, EnumStrSet, SmollerMap, SmollerStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumStr)]
+pub enum Level {
+    #[value("debug")]
+    Debug,
+    #[value("info")]
+    Info,
+    #[value("warn")]
+    Warn,
+    #[value("error")]
+    Error,
+}
+
+#[test]
+fn map() {
+    let mut counts = EnumStrMap::<Level, usize>::new();
+    for word in "error info error debug".split(' ') {
+        *counts.get_or_insert_with(word.parse().unwrap(), || 0) += 1;
+    }
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[Level::Error], 2);
+    assert_eq!(counts.get(Level::Warn), None);
+    assert_eq!(
+        counts.iter().collect::<Vec<_>>(),
+        [(Level::Debug, &1), (Level::Info, &1), (Level::Error, &2)]
+    );
+    assert_eq!(
+        format!("{counts:?}"),
+        r#"{"debug": 1, "info": 1, "error": 2}"#
+    );
+
+    assert_eq!(counts.insert(Level::Info, 5), Some(1));
+    assert_eq!(counts.remove(Level::Debug), Some(1));
+    assert_eq!(counts.remove(Level::Debug), None);
+    assert_eq!(counts.len(), 2);
+    assert_eq!(
+        counts.key_set(),
+        [Level::Info, Level::Error].into_iter().collect()
+    );
+    counts.clear();
+    assert!(counts.is_empty());
+}
+
+#[test]
+fn set() {
+    let a = [Level::Debug, Level::Warn]
+        .into_iter()
+        .collect::<EnumStrSet<_>>();
+    let b = [Level::Warn, Level::Error]
+        .into_iter()
+        .collect::<EnumStrSet<_>>();
+
+    assert_eq!(
+        (&a | &b).iter().collect::<Vec<_>>(),
+        [Level::Debug, Level::Warn, Level::Error]
+    );
+    assert_eq!((&a & &b).iter().collect::<Vec<_>>(), [Level::Warn]);
+    assert_eq!((&a - &b).iter().collect::<Vec<_>>(), [Level::Debug]);
+    assert_eq!(
+        (&a ^ &b).iter().collect::<Vec<_>>(),
+        [Level::Debug, Level::Error]
+    );
+    assert!(a.intersection(&b).is_subset(&a));
+    assert!(EnumStrSet::all().is_superset(&a));
+    assert!(a.difference(&b).is_disjoint(&b));
+    assert_eq!(EnumStrSet::<Level>::all().len(), Level::COUNT);
+    assert_eq!(format!("{a:?}"), r#"{"debug", "warn"}"#);
+
+    let mut c = a.clone();
+    assert!(!c.insert(Level::Debug));
+    assert!(c.remove(Level::Debug));
+    assert!(!c.contains(Level::Debug));
+    assert_eq!(c.len(), 1);
+}
+
+smoller_str::smoller_strings!(
+    Many,
+    [
+        "m0", "m1", "m2", "m3", "m4", "m5", "m6", "m7", "m8", "m9", "m10", "m11", "m12", "m13",
+        "m14", "m15", "m16", "m17", "m18", "m19", "m20", "m21", "m22", "m23", "m24", "m25", "m26",
+        "m27", "m28", "m29", "m30", "m31", "m32", "m33", "m34", "m35", "m36", "m37", "m38", "m39",
+        "m40", "m41", "m42", "m43", "m44", "m45", "m46", "m47", "m48", "m49", "m50", "m51", "m52",
+        "m53", "m54", "m55", "m56", "m57", "m58", "m59", "m60", "m61", "m62", "m63", "m64", "m65",
+        "m66", "m67", "m68", "m69"
+    ]
+);
+
+#[test]
+fn set_over_64_values() {
+    let mut set = EnumStrSet::<ManyRepr>::new();
+    set.insert(ManyRepr::from_index(3).unwrap());
+    set.insert(ManyRepr::from_index(67).unwrap());
+    assert_eq!(
+        set.iter().map(|value| value.as_str()).collect::<Vec<_>>(),
+        ["m3", "m67"]
+    );
+    assert_eq!(EnumStrSet::<ManyRepr>::all().len(), 70);
+}
+
+#[smoller_str]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Header {
+    #[value("Host")]
+    Host,
+    #[value("Accept")]
+    Accept,
+}
+
+#[test]
+fn smoller_map() {
+    let mut headers = SmollerMap::<Header, &str>::new();
+    headers.insert(Header::new("X-Custom"), "1");
+    headers.insert(Header::new("Accept"), "*/*");
+    headers.insert(Header::new("Host"), "example.com");
+
+    assert_eq!(headers.len(), 3);
+    assert_eq!(headers.builtin().len(), 2);
+    assert_eq!(headers[&Header::new("X-Custom")], "1");
+    assert_eq!(
+        headers.get(&Header::from_builtin(HeaderRepr::Host)),
+        Some(&"example.com")
+    );
+    assert_eq!(
+        format!("{headers:?}"),
+        r#"{"Host": "example.com", "Accept": "*/*", "X-Custom": "1"}"#
+    );
+    assert_eq!(
+        headers
+            .iter()
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<_>>(),
+        ["Host", "Accept", "X-Custom"]
+    );
+
+    assert_eq!(headers.remove(&Header::new("X-Custom")), Some("1"));
+    assert_eq!(headers.unknown().count(), 0);
+}
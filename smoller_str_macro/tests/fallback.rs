@@ -0,0 +1,145 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use smoller_str::{smoller_str, EnumStr, SmolStr, SmollerStr};
+
+#[smoller_str]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    #[value("GET")]
+    Get,
+    #[value("POST")]
+    Post,
+    /// Any other method.
+    #[fallback]
+    Other(SmolStr),
+}
+
+#[test]
+fn smol_str_fallback() {
+    assert_eq!(Method::new("GET"), Method::Builtin(MethodRepr::Get));
+    assert_eq!("PUT".parse(), Ok(Method::Other("PUT".into())));
+    assert_eq!(Method::new("PUT").as_str(), "PUT");
+    assert!(!Method::new("PUT").is_heap_allocated());
+    assert!(Method::new("A-VERY-LONG-CUSTOM-HTTP-METHOD").is_heap_allocated());
+    assert_eq!(MethodRepr::COUNT, 2);
+}
+
+/// Keeps the unknown strings it is given, counting how many there were.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Counted(Box<str>);
+
+static COUNTED: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+impl From<&str> for Counted {
+    fn from(s: &str) -> Self {
+        COUNTED.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Self(s.into())
+    }
+}
+
+impl AsRef<str> for Counted {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[smoller_str(case_insensitive)]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Header {
+    #[value("Host")]
+    Host,
+    #[fallback]
+    Custom(Counted),
+}
+
+#[test]
+fn custom_fallback() {
+    let before = COUNTED.load(std::sync::atomic::Ordering::Relaxed);
+    assert_eq!(Header::new("host"), Header::Builtin(HeaderRepr::Host));
+    let custom = Header::new("X-Custom");
+    assert_eq!(custom, Header::Custom(Counted("X-Custom".into())));
+    assert_eq!(custom.as_str(), "X-Custom");
+    assert!(custom.is_heap_allocated());
+    assert_eq!(custom, *"x-custom");
+    assert_eq!(
+        COUNTED.load(std::sync::atomic::Ordering::Relaxed),
+        before + 1
+    );
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn is_status_code(s: &str) -> bool {
+    s.len() == 3 && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[smoller_str(case_insensitive)]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    #[value("content-type")]
+    ContentType,
+    #[value("x-request-id")]
+    RequestId,
+    #[fallback(with = is_status_code)]
+    Status(SmolStr),
+    #[fallback(prefix = "x-")]
+    Vendor(SmolStr),
+    #[fallback(suffix = "-id")]
+    Id(String),
+}
+
+#[test]
+fn classified_fallbacks() {
+    let kinds = [
+        ("Content-Type", FieldKind::Builtin),
+        ("x-request-id", FieldKind::Builtin),
+        ("404", FieldKind::Status),
+        ("4040", FieldKind::Unknown),
+        ("X-Vendor", FieldKind::Vendor),
+        ("x-session-id", FieldKind::Vendor),
+        ("session-ID", FieldKind::Id),
+        ("accept", FieldKind::Unknown),
+    ];
+    for (s, kind) in kinds {
+        let field = Field::new(s);
+        assert_eq!(field.kind(), kind, "{s}");
+        assert_eq!(field, *s);
+    }
+    assert_eq!(Field::new("X-Vendor"), Field::Vendor("X-Vendor".into()));
+    assert_eq!(Field::new("X-Vendor"), Field::new("x-vendor"));
+    assert_eq!(Field::new("Session-Id"), Field::new("session-ID"));
+    assert_ne!(Field::new("x-vendor"), Field::new("x-other"));
+    assert_eq!(hash(&Field::new("X-Vendor")), hash(&Field::new("x-vendor")));
+    assert_eq!(Field::new("session-id").builtin(), None);
+    assert!(Field::new("session-id").is_heap_allocated());
+    assert!(!Field::new("x-short").is_heap_allocated());
+}
+
+#[smoller_str(unknown = "reject")]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Code {
+    #[value("ok")]
+    Ok,
+    #[fallback(with = is_status_code)]
+    Numeric(SmolStr),
+}
+
+#[test]
+fn classified_with_reject() {
+    assert_eq!("200".parse(), Ok(Code::Numeric("200".into())));
+    assert_eq!(Code::try_new("ok"), Ok(Code::Builtin(CodeRepr::Ok)));
+    assert!("fine".parse::<Code>().is_err());
+    assert_eq!(Code::new("200").kind(), CodeKind::Numeric);
+    assert_eq!(
+        serde_json::from_str::<Code>(r#""500""#).unwrap(),
+        Code::Numeric("500".into())
+    );
+    assert!(serde_json::from_str::<Code>(r#""fine""#).is_err());
+}
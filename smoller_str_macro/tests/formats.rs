@@ -0,0 +1,57 @@
+use smoller_str::*;
+
+include_smoller_strings!(JsonMethods, "tests/data/methods.json");
+include_smoller_strings!(CsvMethods, "tests/data/methods.csv");
+include_smoller_strings!(TomlMethods, "tests/data/methods.toml", lookup = "phf");
+include_smoller_strings!(DataMethods, "tests/data/methods.data", format = "json");
+
+#[test]
+fn json() {
+    assert_eq!(
+        JsonMethods::new("GET").builtin(),
+        Some(JsonMethodsRepr::Get)
+    );
+    assert_eq!(
+        JsonMethods::new("post").builtin(),
+        Some(JsonMethodsRepr::Post)
+    );
+    assert_eq!(JsonMethods::new("post").as_str(), "POST");
+    assert_eq!(
+        JsonMethods::new("M-SEARCH").builtin(),
+        Some(JsonMethodsRepr::MSearch)
+    );
+}
+
+#[test]
+fn csv() {
+    assert_eq!(CsvMethods::new("Get").builtin(), Some(CsvMethodsRepr::Get));
+    assert_eq!(
+        CsvMethods::new("POST").builtin(),
+        Some(CsvMethodsRepr::Post)
+    );
+    assert_eq!(
+        CsvMethods::new("M-SEARCH").builtin(),
+        Some(CsvMethodsRepr::MSearch)
+    );
+    assert!(!CsvMethods::new("post").is_builtin_value());
+}
+
+#[test]
+fn toml() {
+    assert_eq!(
+        TomlMethods::new("post").builtin(),
+        Some(TomlMethodsRepr::Post)
+    );
+    assert_eq!(
+        TomlMethods::new("M-SEARCH").builtin(),
+        Some(TomlMethodsRepr::MSearch)
+    );
+}
+
+#[test]
+fn explicit_format() {
+    assert_eq!(
+        DataMethods::new("post").builtin(),
+        Some(DataMethodsRepr::Post)
+    );
+}
@@ -0,0 +1,78 @@
+use smoller_str::smoller_strings;
+
+smoller_strings!(
+    Names,
+    [
+        "content-type",
+        "X-Forwarded-For",
+        "404",
+        "2xx codes",
+        "self",
+        "type",
+        "crate",
+        "contentLength",
+        "café",
+        "Ünïcode",
+        "日本",
+        "+",
+        "<=",
+        "_",
+        "snake_case_value",
+    ]
+);
+
+#[test]
+fn upper_camel() {
+    let names = [
+        (NamesRepr::ContentType, "content-type"),
+        (NamesRepr::XForwardedFor, "X-Forwarded-For"),
+        (NamesRepr::_404, "404"),
+        (NamesRepr::_2XxCodes, "2xx codes"),
+        (NamesRepr::Self_, "self"),
+        (NamesRepr::Type, "type"),
+        (NamesRepr::Crate, "crate"),
+        (NamesRepr::ContentLength, "contentLength"),
+        (NamesRepr::Cafe, "café"),
+        (NamesRepr::Unicode, "Ünïcode"),
+        (NamesRepr::日本, "日本"),
+        (NamesRepr::Plus, "+"),
+        (NamesRepr::LtEq, "<="),
+        (NamesRepr::Underscore, "_"),
+        (NamesRepr::SnakeCaseValue, "snake_case_value"),
+    ];
+    for (name, value) in names {
+        assert_eq!(name.as_str(), value);
+    }
+}
+
+smoller_strings!(
+    Screaming,
+    ["content-type", "fn", "404 not found", "Accept"],
+    ident_case = "ScreamingSnake"
+);
+
+#[test]
+fn screaming_snake() {
+    assert_eq!(ScreamingRepr::CONTENT_TYPE.as_str(), "content-type");
+    assert_eq!(ScreamingRepr::FN.as_str(), "fn");
+    assert_eq!(ScreamingRepr::_404_NOT_FOUND.as_str(), "404 not found");
+    assert_eq!(ScreamingRepr::ACCEPT.as_str(), "Accept");
+}
+
+smoller_strings!(
+    Snake,
+    ["Content-Type", "loop", "Accept" = Accept],
+    ident_case = "Snake",
+    case_insensitive
+);
+
+#[test]
+fn snake_with_other_settings() {
+    assert_eq!(SnakeRepr::content_type.as_str(), "Content-Type");
+    assert_eq!(SnakeRepr::loop_.as_str(), "loop");
+    assert_eq!(SnakeRepr::Accept.as_str(), "Accept");
+    assert_eq!(
+        SnakeRepr::new("content-type"),
+        Some(SnakeRepr::content_type)
+    );
+}
@@ -0,0 +1,30 @@
+use smoller_str::*;
+
+// Relative to this crate's manifest, wherever the compiler is run from.
+include_smoller_strings!(Strings, "tests/data/strings.txt");
+
+#[test]
+fn include_relative_to_manifest() {
+    assert_eq!(Strings::new("GET"), Strings::Builtin(StringsRepr::Get));
+    assert!(!Strings::new("PUT").is_builtin_value());
+}
+
+#[test]
+fn include_line_format() {
+    assert_eq!(
+        Strings::new("M-SEARCH"),
+        Strings::Builtin(StringsRepr::MSearch)
+    );
+    assert_eq!(
+        Strings::new("Content Type"),
+        Strings::Builtin(StringsRepr::ContentType)
+    );
+    assert!(Strings::new("# not a comment").is_builtin_value());
+    assert_eq!(
+        Strings::new("--color=auto"),
+        Strings::Builtin(StringsRepr::ColorAuto)
+    );
+    assert_eq!(Strings::new("x = y").as_str(), "x = y");
+    assert!(Strings::new("x = y").is_builtin_value());
+    assert_eq!(Strings::BUILTIN.len(), 7);
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use smoller_str::*;
+
+#[smoller_str(intern)]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Level {
+    #[value("info")]
+    Info,
+    #[value("warn")]
+    Warn,
+}
+
+// A separate type, so the pool isn't shared with other tests running in parallel.
+#[smoller_str(intern)]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    #[value("stdout")]
+    Stdout,
+}
+
+fn unknown(level: &Level) -> &Arc<str> {
+    match level {
+        Level::Unknown(s) => s,
+        Level::Builtin(_) => panic!("{level:?} is builtin"),
+    }
+}
+
+fn target(target: &Target) -> &Arc<str> {
+    match target {
+        Target::Unknown(s) => s,
+        Target::Builtin(_) => panic!("{target:?} is builtin"),
+    }
+}
+
+#[test]
+fn unknown_values_share_allocation() {
+    let a = Level::new("trace");
+    let b = Level::new(&String::from("trace"));
+    assert!(Arc::ptr_eq(unknown(&a), unknown(&b)));
+    assert_eq!(a, b);
+    assert_ne!(a, Level::new("debug"));
+    assert_eq!(Level::new("info"), Level::Builtin(LevelRepr::Info));
+    assert_eq!(Level::interner().len(), 2);
+}
+
+#[test]
+fn trim_drops_unused_values() {
+    assert_eq!(Target::interner().len(), 0);
+    let kept = Target::new("kept");
+    drop(Target::new("dropped"));
+    Target::new("stdout");
+    assert_eq!(Target::interner().len(), 2);
+    assert_eq!(Target::interner().trim(), 1);
+    assert_eq!(Target::interner().len(), 1);
+    assert_eq!(Target::new("kept"), kept);
+    assert!(Arc::ptr_eq(target(&kept), target(&Target::new("kept"))));
+    assert_eq!(Arc::strong_count(target(&kept)), 2);
+    assert_eq!(Target::interner().len(), 1);
+    assert_eq!(Target::interner().trim(), 0);
+}
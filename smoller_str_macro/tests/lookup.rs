@@ -0,0 +1,79 @@
+use smoller_str::*;
+
+macro_rules! methods {
+    ($name:ident $(, $attr:meta)?) => {
+        #[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+        $(#[$attr])?
+        pub enum $name {
+            #[value("GET")]
+            Get,
+            #[value("PUT")]
+            Put,
+            #[value("POST")]
+            Post,
+            #[value("HEAD")]
+            Head,
+            #[value("PATCH")]
+            Patch,
+            #[value("TRACE")]
+            Trace,
+            #[value("DELETE")]
+            Delete,
+            #[value("CONNECT")]
+            Connect,
+            #[value("OPTIONS")]
+            Options,
+        }
+    };
+}
+
+methods!(MatchMethod);
+methods!(PhfMethod, enum_str(lookup = "phf"));
+methods!(LengthMethod, enum_str(lookup = "length"));
+
+fn check<E: EnumStr + PartialEq + std::fmt::Debug>(new: fn(&str) -> Option<E>) {
+    for value in E::VALUES {
+        assert_eq!(new(value.as_str()), Some(*value));
+    }
+    for miss in ["get", "GETS", "GE", "PATCHES", "OPTION", "X", " ", ""] {
+        assert_eq!(new(miss), None, "{miss:?}");
+    }
+}
+
+#[test]
+fn match_lookup() {
+    check(MatchMethod::new);
+}
+
+#[test]
+fn phf_lookup() {
+    check(PhfMethod::new);
+}
+
+#[test]
+fn length_lookup() {
+    check(LengthMethod::new);
+}
+
+#[test]
+fn lookup_is_const() {
+    const GET: Option<PhfMethod> = PhfMethod::new("GET");
+    const MISS: Option<LengthMethod> = LengthMethod::new("MISS");
+    assert_eq!(GET, Some(PhfMethod::Get));
+    assert_eq!(MISS, None);
+}
+
+#[smoller_str(lookup = "phf")]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Header {
+    #[value("Accept")]
+    Accept,
+    #[value("Content-Type")]
+    ContentType,
+}
+
+#[test]
+fn smoller_str_lookup() {
+    assert_eq!(Header::new("Accept"), Header::Builtin(HeaderRepr::Accept));
+    assert!(Header::new("Content-Length").is_heap_allocated());
+}
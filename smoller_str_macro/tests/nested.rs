@@ -0,0 +1,148 @@
+use smoller_str::{smoller_str, EnumStr, EnumStrSet, SmollerStr};
+
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    #[value("GET", alias = "get")]
+    Get,
+    #[value("POST")]
+    Post,
+}
+
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpAttr {
+    #[value("method")]
+    Method,
+    #[value("status_code", alias = "status")]
+    StatusCode,
+}
+
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attr {
+    #[value("service")]
+    Service,
+    #[nested]
+    Method(HttpMethod),
+    #[nested(prefix = "http.")]
+    Http(HttpAttr),
+}
+
+#[test]
+fn parse() {
+    assert_eq!(Attr::new("service"), Some(Attr::Service));
+    assert_eq!(Attr::new("POST"), Some(Attr::Method(HttpMethod::Post)));
+    assert_eq!(Attr::new("get"), Some(Attr::Method(HttpMethod::Get)));
+    assert_eq!(
+        "http.status_code".parse(),
+        Ok(Attr::Http(HttpAttr::StatusCode))
+    );
+    assert_eq!(
+        Attr::new("http.status"),
+        Some(Attr::Http(HttpAttr::StatusCode))
+    );
+    assert_eq!(Attr::new("method"), None);
+    assert_eq!(Attr::new("http."), None);
+}
+
+#[test]
+fn flattened() {
+    let strs = Attr::VALUES.iter().map(Attr::as_str).collect::<Vec<_>>();
+    assert_eq!(
+        strs,
+        ["service", "GET", "POST", "http.method", "http.status_code"]
+    );
+    assert_eq!(Attr::COUNT, 5);
+    for (i, value) in Attr::VALUES.iter().enumerate() {
+        assert_eq!(value.index(), i);
+        assert_eq!(Attr::from_index(i), Some(*value));
+    }
+    assert_eq!(Attr::from_index(5), None);
+    assert_eq!(Attr::Http(HttpAttr::Method).to_string(), "http.method");
+    assert_eq!(
+        Attr::ALIASES,
+        &[
+            ("get", Attr::Method(HttpMethod::Get)),
+            ("http.status", Attr::Http(HttpAttr::StatusCode)),
+        ]
+    );
+    assert_eq!(Attr::Method(HttpMethod::Get).aliases(), ["get"]);
+    assert_eq!(Attr::Http(HttpAttr::StatusCode).aliases(), ["http.status"]);
+    assert!(Attr::Http(HttpAttr::Method).aliases().is_empty());
+}
+
+#[test]
+fn parse_prefix() {
+    assert_eq!(
+        Attr::parse_prefix("http.status_code=200"),
+        Some((Attr::Http(HttpAttr::StatusCode), "=200"))
+    );
+    assert_eq!(
+        Attr::parse_prefix("http.status=200"),
+        Some((Attr::Http(HttpAttr::StatusCode), "=200"))
+    );
+    assert_eq!(
+        Attr::parse_prefix("GET /"),
+        Some((Attr::Method(HttpMethod::Get), " /"))
+    );
+    assert_eq!(Attr::parse_prefix("http.other"), None);
+}
+
+#[test]
+fn collections() {
+    let set = [Attr::Service, Attr::Http(HttpAttr::Method)]
+        .into_iter()
+        .collect::<EnumStrSet<Attr>>();
+    assert!(set.contains(Attr::Http(HttpAttr::Method)));
+    assert!(!set.contains(Attr::Http(HttpAttr::StatusCode)));
+}
+
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    #[nested(prefix = "attr.")]
+    Attr(Attr),
+}
+
+#[test]
+fn nested_twice() {
+    assert_eq!(
+        Event::new("attr.http.method"),
+        Some(Event::Attr(Attr::Http(HttpAttr::Method)))
+    );
+    assert_eq!(
+        Event::Attr(Attr::Method(HttpMethod::Post)).as_str(),
+        "attr.POST"
+    );
+    assert_eq!(Event::COUNT, 5);
+    assert_eq!(
+        Event::ALIASES,
+        &[
+            ("attr.get", Event::Attr(Attr::Method(HttpMethod::Get))),
+            (
+                "attr.http.status",
+                Event::Attr(Attr::Http(HttpAttr::StatusCode))
+            ),
+        ]
+    );
+    for &(alias, value) in Event::ALIASES {
+        assert_eq!(value.aliases(), [alias]);
+    }
+}
+
+#[smoller_str]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    #[value("host")]
+    Host,
+    #[nested(prefix = "http.")]
+    Http(HttpAttr),
+}
+
+#[test]
+fn smoller_str() {
+    assert_eq!(
+        Field::new("http.method"),
+        Field::Builtin(FieldRepr::Http(HttpAttr::Method))
+    );
+    assert_eq!(Field::new("http.method").as_str(), "http.method");
+    assert_eq!(Field::new("http.other").as_str(), "http.other");
+    assert_eq!(Field::BUILTIN.len(), 3);
+}
@@ -0,0 +1,155 @@
+use smoller_str::{smoller_str, EnumStr};
+
+macro_rules! keywords {
+    ($name:ident $(, $attr:meta)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumStr)]
+        $(#[$attr])?
+        pub enum $name {
+            #[value("in")]
+            In,
+            #[value("int")]
+            Int,
+            #[value("interface")]
+            Interface,
+            #[value("=")]
+            Assign,
+            #[value("==")]
+            Eq,
+            #[value("!=", alias = "<>")]
+            Ne,
+        }
+    };
+}
+
+keywords!(Keyword);
+keywords!(TrieKeyword, enum_str(parse_prefix = "trie"));
+
+fn check_longest_match<K: EnumStr + std::fmt::Debug + PartialEq>() {
+    let k = |s: &str| s.parse::<K>().ok().unwrap();
+    assert_eq!(K::parse_prefix("int x"), Some((k("int"), " x")));
+    assert_eq!(K::parse_prefix("inter"), Some((k("int"), "er")));
+    assert_eq!(K::parse_prefix("interface"), Some((k("interface"), "")));
+    assert_eq!(K::parse_prefix("=== 1"), Some((k("=="), "= 1")));
+    assert_eq!(K::parse_prefix("<> 1"), Some((k("!="), " 1")));
+    assert_eq!(K::parse_prefix("i"), None);
+    assert_eq!(K::parse_prefix("ix"), None);
+    assert_eq!(K::parse_prefix("in"), Some((k("in"), "")));
+    assert_eq!(K::parse_prefix(""), None);
+}
+
+#[test]
+fn longest_match() {
+    check_longest_match::<Keyword>();
+    check_longest_match::<TrieKeyword>();
+}
+
+#[test]
+fn bytes() {
+    assert_eq!(
+        Keyword::parse_prefix_bytes(b"!=\xff"),
+        Some((Keyword::Ne, &b"\xff"[..]))
+    );
+    assert_eq!(Keyword::parse_prefix_bytes(b"\xff"), None);
+}
+
+#[test]
+fn is_const() {
+    const INT: Option<(Keyword, &str)> = Keyword::parse_prefix("int;");
+    assert_eq!(INT, Some((Keyword::Int, ";")));
+    const TRIE_INT: Option<(TrieKeyword, &str)> = TrieKeyword::parse_prefix("int;");
+    assert_eq!(TRIE_INT, Some((TrieKeyword::Int, ";")));
+}
+
+/// Implemented by hand, so it uses the default `parse_prefix_bytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Manual(Keyword);
+
+impl std::fmt::Display for Manual {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for Manual {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, ()> {
+        s.parse().map(Manual)
+    }
+}
+
+impl EnumStr for Manual {
+    const VALUES: &'static [Self] = &[
+        Manual(Keyword::In),
+        Manual(Keyword::Int),
+        Manual(Keyword::Interface),
+        Manual(Keyword::Assign),
+        Manual(Keyword::Eq),
+        Manual(Keyword::Ne),
+    ];
+    const ALIASES: &'static [(&'static str, Self)] = &[("<>", Manual(Keyword::Ne))];
+    fn as_str(&self) -> &'static str {
+        self.0.as_str()
+    }
+}
+
+#[test]
+fn matches_trait_default() {
+    for input in ["int", "inter", "==", "=", "<>=", "x", ""] {
+        assert_eq!(
+            Manual::parse_prefix(input).map(|(m, rest)| (m.0, rest)),
+            Keyword::parse_prefix(input)
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumStr)]
+#[enum_str(case_insensitive)]
+pub enum Method {
+    #[value("GET")]
+    Get,
+    #[value("GETALL")]
+    GetAll,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumStr)]
+#[enum_str(case_insensitive, parse_prefix = "trie")]
+pub enum TrieMethod {
+    #[value("GET")]
+    Get,
+    #[value("GETALL")]
+    GetAll,
+}
+
+#[test]
+fn case_insensitive() {
+    assert_eq!(Method::parse_prefix("get /"), Some((Method::Get, " /")));
+    assert_eq!(
+        Method::parse_prefix("GetAll /"),
+        Some((Method::GetAll, " /"))
+    );
+    assert_eq!(
+        TrieMethod::parse_prefix("get /"),
+        Some((TrieMethod::Get, " /"))
+    );
+    assert_eq!(
+        TrieMethod::parse_prefix("GetAll /"),
+        Some((TrieMethod::GetAll, " /"))
+    );
+}
+
+#[smoller_str]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Token {
+    #[value("(")]
+    Open,
+    #[value(")")]
+    Close,
+    #[value("()")]
+    Unit,
+}
+
+#[test]
+fn smoller_str_repr() {
+    assert_eq!(TokenRepr::parse_prefix("())"), Some((TokenRepr::Unit, ")")));
+    assert_eq!(TokenRepr::parse_prefix(")("), Some((TokenRepr::Close, "(")));
+}
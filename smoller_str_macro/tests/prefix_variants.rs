@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use smoller_str::{collections::SmollerKey, smoller, smoller_str, EnumStr, SmolStr, SmollerMap};
+
+#[smoller_str(case_insensitive)]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Header {
+    #[value("content-type")]
+    ContentType,
+    #[value("x-amz-date")]
+    AmzDate,
+    /// `x-amz-meta-*` user metadata.
+    #[value(prefix = "x-amz-meta-")]
+    AmzMeta(SmolStr),
+    #[value(prefix = "x-amz-")]
+    Amz(SmolStr),
+    #[fallback(prefix = "x-")]
+    Vendor(SmolStr),
+}
+
+#[test]
+fn keeps_the_suffix() {
+    assert_eq!(Header::new("x-amz-acl"), Header::Amz("acl".into()));
+    assert_eq!(
+        Header::new("X-Amz-Meta-Owner"),
+        Header::AmzMeta("Owner".into())
+    );
+    assert_eq!(
+        Header::new("x-amz-date"),
+        Header::Builtin(HeaderRepr::AmzDate)
+    );
+    assert_eq!(Header::new("x-other"), Header::Vendor("x-other".into()));
+    assert_eq!(Header::new("accept").kind(), HeaderKind::Unknown);
+    assert_eq!(Header::new("x-amz-").kind(), HeaderKind::Amz);
+}
+
+#[test]
+fn rebuilds_the_string() {
+    let header = Header::new("X-AMZ-acl");
+    assert_eq!(header.as_str(), "x-amz-acl");
+    assert_eq!(header.to_string(), "x-amz-acl");
+    assert_eq!(header, *"X-Amz-ACL");
+    assert_ne!(header, *"x-amz-acl-2");
+    assert_eq!(&header, "x-amz-acl");
+    assert_eq!(Header::new("Content-Type").as_str(), "content-type");
+    assert!(!Header::new("x-amz-acl").is_heap_allocated());
+    assert_eq!(Header::new("x-amz-acl").builtin(), None);
+}
+
+#[test]
+fn hash() {
+    let headers = [
+        "x-amz-acl",
+        "x-amz-acl",
+        "x-amz-meta-a",
+        "content-type",
+        "Content-Type",
+    ]
+    .into_iter()
+    .map(Header::new)
+    .collect::<HashSet<_>>();
+    assert_eq!(headers.len(), 3);
+}
+
+#[test]
+fn split_prefix() {
+    assert_eq!(Header::new("X-Amz-Acl").split_prefix(), ("x-amz-", "Acl"));
+    assert_eq!(Header::new("x-other").split_prefix(), ("", "x-other"));
+    assert_eq!(
+        Header::new("content-type").split_prefix(),
+        ("", "content-type")
+    );
+}
+
+#[test]
+fn smoller_map() {
+    let mut headers = SmollerMap::<Header, u32>::new();
+    headers.insert(smoller!(Header, "content-type"), 1);
+    headers.insert(Header::new("x-amz-acl"), 2);
+    headers.insert(Header::new("X-Amz-Acl"), 3);
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers.get(&Header::new("Content-Type")), Some(&1));
+    assert_eq!(headers.get(&Header::Amz("ACL".into())), Some(&3));
+    assert_eq!(SmollerKey::to_str(&Header::Amz("acl".into())), "x-amz-acl");
+}
+
+#[test]
+fn serde() {
+    let header: Header = serde_json::from_str(r#""x-amz-meta-owner""#).unwrap();
+    assert_eq!(header, Header::AmzMeta("owner".into()));
+    assert_eq!(
+        serde_json::to_string(&header).unwrap(),
+        r#""x-amz-meta-owner""#
+    );
+}
+
+#[smoller_str(unknown = "reject")]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Scope {
+    #[value("read")]
+    Read,
+    #[value(prefix = "repo:")]
+    Repo(String),
+}
+
+#[test]
+fn reject() {
+    assert_eq!("repo:crate".parse(), Ok(Scope::Repo("crate".into())));
+    assert_eq!(Scope::try_new("read"), Ok(Scope::Builtin(ScopeRepr::Read)));
+    assert!("write".parse::<Scope>().is_err());
+    assert!(serde_json::from_str::<Scope>(r#""write""#).is_err());
+}
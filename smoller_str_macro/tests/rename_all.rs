@@ -0,0 +1,61 @@
+use smoller_str::{smoller_str, EnumStr, SmollerStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumStr)]
+#[enum_str(rename_all = "kebab-case")]
+pub enum Header {
+    ContentType,
+    XForwardedFor,
+    Http2Settings,
+    #[value("ETag")]
+    ETag,
+    r#Type,
+}
+
+#[test]
+fn kebab_case() {
+    assert_eq!(Header::ContentType.as_str(), "content-type");
+    assert_eq!(Header::XForwardedFor.as_str(), "x-forwarded-for");
+    assert_eq!(Header::Http2Settings.as_str(), "http2-settings");
+    assert_eq!(Header::ETag.as_str(), "ETag");
+    assert_eq!(Header::Type.as_str(), "type");
+    assert_eq!("content-type".parse(), Ok(Header::ContentType));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumStr)]
+#[enum_str(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Level {
+    Debug,
+    NotSet,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumStr)]
+#[enum_str(rename_all = "lowercase", case_insensitive)]
+pub enum Method {
+    Get,
+    #[value("POST", alias = "send")]
+    Post,
+    MSearch,
+}
+
+#[test]
+fn other_cases() {
+    assert_eq!(Level::NotSet.as_str(), "NOT_SET");
+    assert_eq!(Method::MSearch.as_str(), "msearch");
+    assert_eq!(Method::new("GET"), Some(Method::Get));
+    assert_eq!(Method::Post.as_str(), "POST");
+}
+
+#[smoller_str(rename_all = "camelCase")]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    UserName,
+    #[value("id")]
+    Identifier,
+}
+
+#[test]
+fn smoller_str_attribute() {
+    assert_eq!(Field::new("userName").builtin(), Some(FieldRepr::UserName));
+    assert_eq!(Field::new("id").builtin(), Some(FieldRepr::Identifier));
+    assert!(Field::new("UserName").builtin().is_none());
+}
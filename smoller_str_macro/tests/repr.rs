@@ -0,0 +1,130 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use smoller_str::*;
+
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    #[value("GET")]
+    Get,
+    #[value("POST")]
+    Post,
+}
+
+#[smoller_str(storage = SmolStr)]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Header {
+    #[value("Accept")]
+    Accept,
+    #[value("Content-Type", alias = "content-type")]
+    ContentType,
+}
+
+#[smoller_str(storage = SmolStr, case_insensitive)]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Keyword {
+    #[value("SELECT")]
+    Select,
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+const INPUTS: &[&str] = &[
+    "Accept",
+    "Content-Type",
+    "content-type",
+    "accept",
+    "X-Short",
+    "X-A-Header-Name-That-Does-Not-Fit-Inline",
+    "SELECT",
+    "select",
+    "",
+];
+
+fn assert_same<W: SmollerStr + Hash + Eq + std::fmt::Debug>()
+where
+    W::Builtin: PartialEq + std::fmt::Debug,
+{
+    for input in INPUTS {
+        let wrapper = W::new(input);
+        let repr = Repr::<W::Builtin>::new(input);
+        assert_eq!(wrapper.as_str(), repr.as_str(), "{input:?}");
+        assert_eq!(wrapper.to_string(), repr.to_string(), "{input:?}");
+        assert_eq!(wrapper.builtin(), repr.builtin(), "{input:?}");
+        assert_eq!(wrapper.is_heap_allocated(), repr.is_heap_allocated(), "{input:?}");
+        assert_eq!(wrapper.is_builtin_value(), repr.is_builtin_value(), "{input:?}");
+        assert_eq!(hash(&wrapper), hash(&repr), "{input:?}");
+        for other in INPUTS {
+            assert_eq!(
+                wrapper == W::new(other),
+                repr == Repr::new(other),
+                "{input:?} == {other:?}"
+            );
+        }
+    }
+    assert_eq!(
+        W::builtin_values().map(|w| w.to_string()).collect::<Vec<_>>(),
+        Repr::<W::Builtin>::builtin_values()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn repr_matches_smoller_str() {
+    assert_same::<Header>();
+    assert_same::<Keyword>();
+}
+
+#[test]
+fn repr_conversions() {
+    let get: Repr<Method> = Method::Get.into();
+    assert_eq!(get, Repr::Smoller(Method::Get));
+    assert_eq!(Repr::<Method>::from("POST"), Repr::Smoller(Method::Post));
+    assert_eq!(Repr::<Method>::from(String::from("PUT")), "PUT");
+    assert_eq!(Repr::<Method>::from(SmolStr::new("GET")), Method::Get.as_str());
+    assert_eq!("PATCH".parse::<Repr<Method>>().unwrap().as_str(), "PATCH");
+    assert_eq!(get.clone(), get);
+}
+
+#[test]
+fn repr_map_get_str() {
+    let mut map = HashMap::new();
+    map.insert(Repr::<Method>::new("GET"), 1);
+    map.insert(Repr::<Method>::new("PATCH"), 2);
+    assert_eq!(map.get("GET"), Some(&1));
+    assert_eq!(map.get("PATCH"), Some(&2));
+    assert_eq!(map.get("get"), None);
+}
+
+#[test]
+fn repr_map_ignores_case() {
+    let mut map = HashMap::new();
+    map.insert(Repr::<KeywordRepr>::new("select"), 1);
+    map.insert(Repr::<KeywordRepr>::new("LIMIT"), 2);
+    assert_eq!(map.get(&Repr::new("SELECT")), Some(&1));
+    assert_eq!(map.get(&Repr::new("limit")), Some(&2));
+    assert_eq!(map.get(&Repr::new("offset")), None);
+}
+
+#[test]
+fn repr_ord_ignores_case() {
+    let a = Repr::<KeywordRepr>::new("Apple");
+    let b = Repr::<KeywordRepr>::new("banana");
+    assert!(a < b);
+    assert_eq!(a.cmp(&Repr::new("APPLE")), std::cmp::Ordering::Equal);
+    assert!(Repr::<Method>::new("Z") < Repr::new("a"));
+}
+
+#[test]
+#[allow(deprecated)]
+fn builtin_values() {
+    assert_eq!(<Repr<Method> as SmollerStr>::BUILTIN, Method::VALUES);
+    assert_eq!(<Header as SmollerStr>::BUILTIN, HeaderRepr::VALUES);
+}
@@ -0,0 +1,107 @@
+use smoller_str::{smoller_str, EnumStr, SmollerStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumStr)]
+pub enum Level {
+    #[value("INFO")]
+    Info,
+    #[value("WARN", alias = "WARNING")]
+    Warn,
+    #[value("ERROR")]
+    Error,
+}
+
+#[test]
+fn finds_every_value() {
+    let line = "ERROR: disk full (was WARN, then INFO)";
+    let found = Level::find_iter(line).collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [
+            (0..5, Level::Error),
+            (22..26, Level::Warn),
+            (33..37, Level::Info)
+        ]
+    );
+    assert_eq!(Level::find_iter("nothing here").next(), None);
+}
+
+#[test]
+fn longest_alias_wins() {
+    let found = Level::find_iter("WARNING").collect::<Vec<_>>();
+    assert_eq!(found, [(0..7, Level::Warn)]);
+}
+
+#[test]
+fn built_once() {
+    assert!(std::ptr::eq(Level::searcher(), Level::searcher()));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumStr)]
+#[enum_str(case_insensitive)]
+pub enum Method {
+    #[value("GET")]
+    Get,
+    #[value("POST")]
+    Post,
+}
+
+#[test]
+fn case_insensitive() {
+    let found = Method::find_iter("get then Post").collect::<Vec<_>>();
+    assert_eq!(found, [(0..3, Method::Get), (9..13, Method::Post)]);
+}
+
+#[smoller_str]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Tag {
+    #[value("todo")]
+    Todo,
+    #[value("fixme")]
+    Fixme,
+}
+
+#[test]
+fn smoller_str_repr() {
+    let text = "fixme: todo";
+    let tags = TagRepr::find_iter(text)
+        .map(|(range, _)| Tag::new(&text[range]))
+        .collect::<Vec<_>>();
+    assert_eq!(tags[0].builtin(), Some(TagRepr::Fixme));
+    assert_eq!(tags[1].builtin(), Some(TagRepr::Todo));
+}
+
+/// Implemented by hand, so it uses the default `searcher`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Manual(Level);
+
+impl std::fmt::Display for Manual {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for Manual {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, ()> {
+        s.parse().map(Manual)
+    }
+}
+
+impl EnumStr for Manual {
+    const VALUES: &'static [Self] = &[Manual(Level::Info), Manual(Level::Error)];
+    fn as_str(&self) -> &'static str {
+        self.0.as_str()
+    }
+}
+
+#[test]
+fn default_searcher() {
+    let found = Manual::find_iter("INFO ERROR WARN").collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [(0..4, Manual(Level::Info)), (5..10, Manual(Level::Error))]
+    );
+    assert!(std::ptr::eq(Manual::searcher(), Manual::searcher()));
+    // Types do not share an automaton.
+    assert_eq!(Level::find_iter("WARN").count(), 1);
+}
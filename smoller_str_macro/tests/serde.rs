@@ -0,0 +1,46 @@
+use smoller_str::*;
+
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Method {
+    #[value("GET")]
+    Get,
+    #[value("POST")]
+    Post,
+}
+
+#[smoller_str]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Header {
+    #[value("Content-Type")]
+    ContentType,
+    #[value("Accept")]
+    Accept,
+}
+
+#[test]
+fn enum_str_round_trip() {
+    assert_eq!(serde_json::to_string(&Method::Post).unwrap(), r#""POST""#);
+    let method: Method = serde_json::from_str(r#""GET""#).unwrap();
+    assert_eq!(method, Method::Get);
+}
+
+#[test]
+fn enum_str_rejects_unknown() {
+    let err = serde_json::from_str::<Method>(r#""PUT""#).unwrap_err();
+    assert!(err.to_string().contains("one of `GET`, `POST`"), "{err}");
+}
+
+#[test]
+fn smoller_str_builtin() {
+    let header: Header = serde_json::from_str(r#""Accept""#).unwrap();
+    assert_eq!(header, Header::Builtin(HeaderRepr::Accept));
+    assert!(!header.is_heap_allocated());
+    assert_eq!(serde_json::to_string(&header).unwrap(), r#""Accept""#);
+}
+
+#[test]
+fn smoller_str_unknown() {
+    let header: Header = serde_json::from_str(r#""X-Custom""#).unwrap();
+    assert!(matches!(&header, Header::Unknown(s) if &**s == "X-Custom"));
+    assert_eq!(serde_json::to_string(&header).unwrap(), r#""X-Custom""#);
+}
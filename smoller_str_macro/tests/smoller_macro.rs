@@ -0,0 +1,49 @@
+use smoller_str::{smoller, smoller_str, smoller_strings, EnumStr, SmollerStr};
+
+smoller_strings!(Method, ["GET", "POST", "M-SEARCH"]);
+
+static POST: Method = smoller!(Method, "POST");
+const SEARCH: Method = smoller!(Method, "M-SEARCH");
+
+#[test]
+fn builtin_literals() {
+    assert_eq!(smoller!(Method, "GET"), Method::Builtin(MethodRepr::Get));
+    assert_eq!(POST.builtin(), Some(MethodRepr::Post));
+    assert_eq!(SEARCH.as_str(), "M-SEARCH");
+}
+
+#[test]
+fn const_constructors() {
+    const GET: Method = Method::from_builtin(MethodRepr::Get);
+    const PUT: Option<Method> = Method::try_builtin("PUT");
+    assert_eq!(GET, Method::new("GET"));
+    assert_eq!(PUT, None);
+    assert_eq!(Method::try_builtin("POST"), Some(POST.clone()));
+}
+
+#[smoller_str(case_insensitive)]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Header {
+    #[value("Content-Type")]
+    ContentType,
+}
+
+#[test]
+fn case_insensitive() {
+    assert_eq!(
+        smoller!(Header, "content-type"),
+        Header::Builtin(HeaderRepr::ContentType)
+    );
+}
+
+mod other {
+    use smoller_str::smoller;
+
+    // The functions of the repr are private to the module of the type.
+    pub static GET: super::Method = smoller!(super::Method, "GET");
+}
+
+#[test]
+fn other_module() {
+    assert_eq!(other::GET.builtin(), Some(MethodRepr::Get));
+}
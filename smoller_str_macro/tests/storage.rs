@@ -0,0 +1,56 @@
+use smoller_str::*;
+
+macro_rules! storage {
+    ($name:ident, $storage:ident) => {
+        #[smoller_str(storage = $storage)]
+        #[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+        pub enum $name {
+            #[value("builtin")]
+            Builtin,
+        }
+    };
+}
+
+storage!(InSmolStr, SmolStr);
+storage!(InArc, Arc);
+storage!(InRc, Rc);
+storage!(InBox, Box);
+storage!(InString, String);
+
+const LONG: &str = "a string that is too long to be stored inline";
+
+fn check<T: SmollerStr + std::fmt::Debug>() {
+    let builtin = T::new("builtin");
+    assert!(builtin.is_builtin_value());
+    assert!(!builtin.is_heap_allocated());
+
+    let long = T::new(LONG);
+    assert!(!long.is_builtin_value());
+    assert!(long.is_heap_allocated());
+    assert_eq!(long.as_str(), LONG);
+}
+
+#[test]
+fn storage_types() {
+    check::<InSmolStr>();
+    check::<InArc>();
+    check::<InRc>();
+    check::<InBox>();
+    check::<InString>();
+}
+
+#[test]
+fn smol_str_inline() {
+    let short = InSmolStr::new("short");
+    assert!(matches!(&short, InSmolStr::Unknown(s) if s == "short"));
+    assert!(!short.is_heap_allocated());
+    assert!(InRc::new("short").is_heap_allocated());
+}
+
+#[test]
+fn empty_strings() {
+    assert!(!InString::new("").is_heap_allocated());
+    assert!(!InBox::new("").is_heap_allocated());
+    assert!(!InSmolStr::new("").is_heap_allocated());
+    assert!(InArc::new("").is_heap_allocated());
+}
@@ -0,0 +1,64 @@
+use smoller_str::{smoller_str, EnumStr, Repr, SmollerStr, UnknownValue};
+
+#[smoller_str(unknown = "reject")]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Method {
+    #[value("GET")]
+    Get,
+    #[value("POST")]
+    Post,
+}
+
+#[test]
+fn parse_rejects_unknown() {
+    assert_eq!("GET".parse(), Ok(Method::Builtin(MethodRepr::Get)));
+    let err = "PUT".parse::<Method>().unwrap_err();
+    assert_eq!(err.input(), "PUT");
+    assert_eq!(err.expected(), [MethodRepr::Get, MethodRepr::Post]);
+    assert_eq!(
+        err.to_string(),
+        "unknown value `PUT`, expected one of `GET`, `POST`"
+    );
+    assert_eq!(
+        Method::try_from("POST"),
+        Ok(Method::Builtin(MethodRepr::Post))
+    );
+    assert!(Method::try_from("PATCH").is_err());
+}
+
+#[test]
+#[should_panic(expected = "unknown value `PUT`, expected one of `GET`, `POST`")]
+fn new_panics_on_unknown() {
+    Method::new("PUT");
+}
+
+#[test]
+fn borrows_rejected_input() {
+    let input = String::from("a rejected value longer than an inline string");
+    let err = Method::try_new(&input).unwrap_err();
+    assert!(std::ptr::eq(err.input(), input.as_str()));
+    assert!(!Method::new("GET").is_heap_allocated());
+}
+
+#[test]
+fn serde_rejects_unknown() {
+    let get: Method = serde_json::from_str(r#""GET""#).unwrap();
+    assert_eq!(get, Method::Builtin(MethodRepr::Get));
+    let err = serde_json::from_str::<Method>(r#""PUT""#).unwrap_err();
+    assert!(err.to_string().contains("one of `GET`, `POST`"), "{err}");
+}
+
+#[smoller_str]
+#[derive(EnumStr, Debug, Clone, Copy, PartialEq)]
+pub enum Lenient {
+    #[value("a")]
+    A,
+}
+
+#[test]
+fn try_new() {
+    assert_eq!(Lenient::try_new("a"), Ok(Lenient::Builtin(LenientRepr::A)));
+    assert_eq!(Lenient::try_new("b"), Err(UnknownValue::new("b")));
+    assert!(Repr::<LenientRepr>::try_new("b").is_err());
+    assert_eq!("b".parse::<Lenient>().unwrap().as_str(), "b");
+}
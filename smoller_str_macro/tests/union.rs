@@ -0,0 +1,98 @@
+use smoller_str::{smoller_strings, EnumStr, SmollerStr};
+
+smoller_strings!(Methods, ["GET", "POST"]);
+
+smoller_strings!(Headers, ["host", "accept"]);
+
+smoller_strings!(All, [..Methods, ..Headers, "extra"]);
+
+smoller_strings!(Reprs, ["first", ..MethodsRepr, "last"]);
+
+mod http {
+    use smoller_str::smoller_strings;
+
+    smoller_strings!(Methods, ["GET", "POST"]);
+}
+
+smoller_strings!(Remote, [..http::Methods, "extra"]);
+
+#[test]
+fn union() {
+    assert_eq!(
+        All::new("POST"),
+        All::Builtin(AllRepr::Methods(MethodsRepr::Post))
+    );
+    assert_eq!(All::new("extra"), All::Builtin(AllRepr::Extra));
+    assert_eq!(All::new("host").as_str(), "host");
+    assert!(All::new("other").builtin().is_none());
+    let strs = AllRepr::VALUES
+        .iter()
+        .map(AllRepr::as_str)
+        .collect::<Vec<_>>();
+    assert_eq!(strs, ["GET", "POST", "host", "accept", "extra"]);
+    assert_eq!(All::BUILTIN.len(), 5);
+    assert_eq!(AllRepr::Extra.index(), 4);
+    assert_eq!(
+        AllRepr::from_index(2),
+        Some(AllRepr::Headers(HeadersRepr::Host))
+    );
+}
+
+#[test]
+fn declaration_order() {
+    let strs = ReprsRepr::VALUES
+        .iter()
+        .map(ReprsRepr::as_str)
+        .collect::<Vec<_>>();
+    assert_eq!(strs, ["first", "GET", "POST", "last"]);
+    for (i, value) in ReprsRepr::VALUES.iter().enumerate() {
+        assert_eq!(value.index(), i);
+    }
+    assert_eq!(
+        Reprs::new("GET"),
+        Reprs::Builtin(ReprsRepr::MethodsRepr(MethodsRepr::Get))
+    );
+}
+
+#[test]
+fn conversions() {
+    let all = All::from(MethodsRepr::Get);
+    assert_eq!(all, All::new("GET"));
+    assert_eq!(MethodsRepr::try_from(all), Ok(MethodsRepr::Get));
+    assert_eq!(
+        AllRepr::from(HeadersRepr::Accept),
+        AllRepr::Headers(HeadersRepr::Accept)
+    );
+    assert_eq!(
+        HeadersRepr::try_from(AllRepr::Headers(HeadersRepr::Host)),
+        Ok(HeadersRepr::Host)
+    );
+
+    let err = MethodsRepr::try_from(All::new("host")).unwrap_err();
+    assert_eq!(err.input(), "host");
+    assert_eq!(
+        err.to_string(),
+        "unknown value `host`, expected one of `GET`, `POST`"
+    );
+    assert!(MethodsRepr::try_from(All::new("other")).is_err());
+    assert!(MethodsRepr::try_from(AllRepr::Extra).is_err());
+}
+
+#[test]
+fn niche() {
+    assert_eq!(std::mem::size_of::<ReprsRepr>(), 1);
+}
+
+#[test]
+fn other_module() {
+    assert_eq!(
+        Remote::new("POST"),
+        Remote::Builtin(RemoteRepr::Methods(http::MethodsRepr::Post))
+    );
+    assert_eq!(Remote::new("extra").as_str(), "extra");
+    assert_eq!(RemoteRepr::Extra.index(), 2);
+    assert_eq!(
+        RemoteRepr::parse_prefix("GET /"),
+        Some((RemoteRepr::Methods(http::MethodsRepr::Get), " /"))
+    );
+}
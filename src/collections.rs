@@ -0,0 +1,541 @@
+//! Maps and sets keyed by [`EnumStr`] values, stored in arrays indexed by
+//! [`EnumStr::index`].
+use std::{
+    borrow::Cow,
+    collections::{hash_map, HashMap},
+    fmt, hash,
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::{BitAnd, BitOr, BitXor, Index, IndexMut, Sub},
+    str::FromStr,
+};
+
+use crate::{EnumStr, SmollerStr};
+
+/// A map from the values of `E`, with a slot for every value.
+pub struct EnumStrMap<E: EnumStr, V> {
+    slots: Box<[Option<V>]>,
+    len: usize,
+    _key: PhantomData<E>,
+}
+
+impl<E: EnumStr, V> EnumStrMap<E, V> {
+    pub fn new() -> Self {
+        Self {
+            slots: std::iter::repeat_with(|| None).take(E::COUNT).collect(),
+            len: 0,
+            _key: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains_key(&self, key: E) -> bool {
+        self.slots[key.index()].is_some()
+    }
+
+    pub fn get(&self, key: E) -> Option<&V> {
+        self.slots[key.index()].as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: E) -> Option<&mut V> {
+        self.slots[key.index()].as_mut()
+    }
+
+    /// Returns the value for `key`, inserting the result of `default` if there is none.
+    pub fn get_or_insert_with(&mut self, key: E, default: impl FnOnce() -> V) -> &mut V {
+        let slot = &mut self.slots[key.index()];
+        if slot.is_none() {
+            self.len += 1;
+        }
+        slot.get_or_insert_with(default)
+    }
+
+    /// Inserts `value` for `key`, returning the previous value.
+    pub fn insert(&mut self, key: E, value: V) -> Option<V> {
+        let old = self.slots[key.index()].replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    pub fn remove(&mut self, key: E) -> Option<V> {
+        let old = self.slots[key.index()].take();
+        if old.is_some() {
+            self.len -= 1;
+        }
+        old
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        self.len = 0;
+    }
+
+    /// The entries, in the order of [`EnumStr::VALUES`].
+    pub fn iter(&self) -> impl Iterator<Item = (E, &V)> + '_ {
+        E::VALUES
+            .iter()
+            .zip(self.slots.iter())
+            .filter_map(|(&key, slot)| Some((key, slot.as_ref()?)))
+    }
+
+    /// The entries, in the order of [`EnumStr::VALUES`].
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (E, &mut V)> + '_ {
+        E::VALUES
+            .iter()
+            .zip(self.slots.iter_mut())
+            .filter_map(|(&key, slot)| Some((key, slot.as_mut()?)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = E> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.slots.iter().flatten()
+    }
+
+    /// The set of keys with a value.
+    pub fn key_set(&self) -> EnumStrSet<E> {
+        self.keys().collect()
+    }
+}
+
+impl<E: EnumStr, V> Default for EnumStrMap<E, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: EnumStr, V: Clone> Clone for EnumStrMap<E, V> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            len: self.len,
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<E: EnumStr, V: PartialEq> PartialEq for EnumStrMap<E, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.slots == other.slots
+    }
+}
+
+impl<E: EnumStr, V: Eq> Eq for EnumStrMap<E, V> {}
+
+impl<E: EnumStr, V: fmt::Debug> fmt::Debug for EnumStrMap<E, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(key, value)| (key.as_str(), value)))
+            .finish()
+    }
+}
+
+impl<E: EnumStr, V> Index<E> for EnumStrMap<E, V> {
+    type Output = V;
+
+    fn index(&self, key: E) -> &V {
+        self.get(key)
+            .unwrap_or_else(|| panic!("no entry for `{}`", key.as_str()))
+    }
+}
+
+impl<E: EnumStr, V> IndexMut<E> for EnumStrMap<E, V> {
+    fn index_mut(&mut self, key: E) -> &mut V {
+        self.get_mut(key)
+            .unwrap_or_else(|| panic!("no entry for `{}`", key.as_str()))
+    }
+}
+
+impl<E: EnumStr, V> FromIterator<(E, V)> for EnumStrMap<E, V> {
+    fn from_iter<I: IntoIterator<Item = (E, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<E: EnumStr, V> Extend<(E, V)> for EnumStrMap<E, V> {
+    fn extend<I: IntoIterator<Item = (E, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// A set of the values of `E`, stored as one bit per value.
+pub struct EnumStrSet<E: EnumStr> {
+    words: Box<[u64]>,
+    _value: PhantomData<E>,
+}
+
+impl<E: EnumStr> EnumStrSet<E> {
+    pub fn new() -> Self {
+        Self {
+            words: vec![0; E::COUNT.div_ceil(64)].into_boxed_slice(),
+            _value: PhantomData,
+        }
+    }
+
+    /// The set of every value.
+    pub fn all() -> Self {
+        E::VALUES.iter().copied().collect()
+    }
+
+    fn bit(value: E) -> (usize, u64) {
+        let index = value.index();
+        (index / 64, 1 << (index % 64))
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn contains(&self, value: E) -> bool {
+        let (word, bit) = Self::bit(value);
+        self.words[word] & bit != 0
+    }
+
+    /// Adds `value`, returning whether it was not already in the set.
+    pub fn insert(&mut self, value: E) -> bool {
+        let (word, bit) = Self::bit(value);
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    /// Removes `value`, returning whether it was in the set.
+    pub fn remove(&mut self, value: E) -> bool {
+        let (word, bit) = Self::bit(value);
+        let removed = self.words[word] & bit != 0;
+        self.words[word] &= !bit;
+        removed
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+    }
+
+    /// The values in the set, in the order of [`EnumStr::VALUES`].
+    pub fn iter(&self) -> impl Iterator<Item = E> + '_ {
+        E::VALUES
+            .iter()
+            .copied()
+            .filter(move |&value| self.contains(value))
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            _value: PhantomData,
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+}
+
+impl<E: EnumStr> Default for EnumStrSet<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: EnumStr> Clone for EnumStrSet<E> {
+    fn clone(&self) -> Self {
+        Self {
+            words: self.words.clone(),
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<E: EnumStr> PartialEq for EnumStrSet<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.words == other.words
+    }
+}
+
+impl<E: EnumStr> Eq for EnumStrSet<E> {}
+
+impl<E: EnumStr> hash::Hash for EnumStrSet<E> {
+    fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
+        self.words.hash(hasher)
+    }
+}
+
+impl<E: EnumStr> fmt::Debug for EnumStrSet<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|value| value.as_str()))
+            .finish()
+    }
+}
+
+impl<E: EnumStr> FromIterator<E> for EnumStrSet<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<E: EnumStr> Extend<E> for EnumStrSet<E> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+macro_rules! set_op {
+    ($trait:ident, $fn:ident, $method:ident) => {
+        impl<E: EnumStr> $trait for &EnumStrSet<E> {
+            type Output = EnumStrSet<E>;
+
+            fn $fn(self, other: Self) -> EnumStrSet<E> {
+                self.$method(other)
+            }
+        }
+    };
+}
+
+set_op! {BitOr, bitor, union}
+set_op! {BitAnd, bitand, intersection}
+set_op! {Sub, sub, difference}
+set_op! {BitXor, bitxor, symmetric_difference}
+
+/// What [`SmollerMap`] needs of its keys, which every [`SmollerStr`] has. The `#[smoller_str]`
+/// types with `#[value(prefix = "...")]` variants implement it too, since they can't lend out
+/// their full string to implement `SmollerStr`.
+///
+/// It is not re-exported from the crate root, where its methods would clash with those of
+/// `SmollerStr`.
+pub trait SmollerKey: FromStr + Clone + fmt::Display + 'static {
+    /// The set of builtin values.
+    type Builtin: EnumStr;
+
+    fn from_builtin(value: Self::Builtin) -> Self;
+    /// The builtin value, if this is one.
+    fn builtin(&self) -> Option<Self::Builtin>;
+    /// The full string, which is only allocated when it is kept in two parts.
+    fn to_str(&self) -> Cow<'_, str>;
+}
+
+impl<T: SmollerStr> SmollerKey for T {
+    type Builtin = T::Builtin;
+
+    fn from_builtin(value: Self::Builtin) -> Self {
+        SmollerStr::from_builtin(value)
+    }
+
+    fn builtin(&self) -> Option<Self::Builtin> {
+        SmollerStr::builtin(self)
+    }
+
+    fn to_str(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.as_str())
+    }
+}
+
+/// A map keyed by a [`SmollerStr`], or any other [`SmollerKey`], with the builtin keys in an
+/// [`EnumStrMap`] and the others in a [`HashMap`].
+pub struct SmollerMap<K: SmollerKey, V> {
+    builtin: EnumStrMap<K::Builtin, V>,
+    unknown: HashMap<K, V>,
+}
+
+impl<K: SmollerKey + hash::Hash + Eq, V> SmollerMap<K, V> {
+    pub fn new() -> Self {
+        Self {
+            builtin: EnumStrMap::new(),
+            unknown: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.builtin.len() + self.unknown.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.builtin.is_empty() && self.unknown.is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match key.builtin() {
+            Some(builtin) => self.builtin.get(builtin),
+            None => self.unknown.get(key),
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match key.builtin() {
+            Some(builtin) => self.builtin.get_mut(builtin),
+            None => self.unknown.get_mut(key),
+        }
+    }
+
+    /// Returns the value for `key`, inserting the result of `default` if there is none.
+    pub fn get_or_insert_with(&mut self, key: K, default: impl FnOnce() -> V) -> &mut V {
+        match key.builtin() {
+            Some(builtin) => self.builtin.get_or_insert_with(builtin, default),
+            None => self.unknown.entry(key).or_insert_with(default),
+        }
+    }
+
+    /// Inserts `value` for `key`, returning the previous value.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match key.builtin() {
+            Some(builtin) => self.builtin.insert(builtin, value),
+            None => self.unknown.insert(key, value),
+        }
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        match key.builtin() {
+            Some(builtin) => self.builtin.remove(builtin),
+            None => self.unknown.remove(key),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.builtin.clear();
+        self.unknown.clear();
+    }
+
+    /// The builtin entries, in the order of [`EnumStr::VALUES`].
+    pub fn builtin(&self) -> &EnumStrMap<K::Builtin, V> {
+        &self.builtin
+    }
+
+    /// The entries whose key is not a builtin value, in no particular order.
+    pub fn unknown(&self) -> hash_map::Iter<'_, K, V> {
+        self.unknown.iter()
+    }
+
+    /// The builtin entries in the order of [`EnumStr::VALUES`], then the others.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &V)> + '_ {
+        let builtin = self
+            .builtin
+            .iter()
+            .map(|(key, value)| (K::from_builtin(key), value));
+        let unknown = self.unknown.iter().map(|(key, value)| (key.clone(), value));
+        builtin.chain(unknown)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.builtin.values().chain(self.unknown.values())
+    }
+}
+
+impl<K: SmollerKey + hash::Hash + Eq, V> Default for SmollerMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: SmollerKey + hash::Hash + Eq, V: Clone> Clone for SmollerMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            builtin: self.builtin.clone(),
+            unknown: self.unknown.clone(),
+        }
+    }
+}
+
+impl<K: SmollerKey + hash::Hash + Eq, V: PartialEq> PartialEq for SmollerMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.builtin == other.builtin && self.unknown == other.unknown
+    }
+}
+
+impl<K: SmollerKey + hash::Hash + Eq, V: Eq> Eq for SmollerMap<K, V> {}
+
+impl<K: SmollerKey + hash::Hash + Eq, V: fmt::Debug> fmt::Debug for SmollerMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut map = f.debug_map();
+        for (key, value) in self.builtin.iter() {
+            map.entry(&key.as_str(), value);
+        }
+        for (key, value) in self.unknown.iter() {
+            map.entry(&key.to_str(), value);
+        }
+        map.finish()
+    }
+}
+
+impl<K: SmollerKey + hash::Hash + Eq, V> Index<&K> for SmollerMap<K, V> {
+    type Output = V;
+
+    fn index(&self, key: &K) -> &V {
+        self.get(key)
+            .unwrap_or_else(|| panic!("no entry for `{}`", key))
+    }
+}
+
+impl<K: SmollerKey + hash::Hash + Eq, V> FromIterator<(K, V)> for SmollerMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: SmollerKey + hash::Hash + Eq, V> Extend<(K, V)> for SmollerMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
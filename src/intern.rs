@@ -0,0 +1,65 @@
+//! Global pools for the unknown values of `#[smoller_str(intern)]` types.
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// A pool of shared strings, so that equal strings share a single allocation.
+///
+/// `#[smoller_str(intern)]` generates one `static` pool per type, reachable through the
+/// generated `interner()` function.
+pub struct Interner {
+    pool: Mutex<Option<HashSet<Arc<str>>>>,
+}
+
+impl Interner {
+    pub const fn new() -> Self {
+        Self {
+            pool: Mutex::new(None),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<HashSet<Arc<str>>>> {
+        self.pool.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns the pooled copy of `s`, adding it to the pool if needed.
+    pub fn intern(&self, s: &str) -> Arc<str> {
+        let mut pool = self.lock();
+        let pool = pool.get_or_insert_with(HashSet::new);
+        if let Some(interned) = pool.get(s) {
+            return interned.clone();
+        }
+        let interned: Arc<str> = s.into();
+        pool.insert(interned.clone());
+        interned
+    }
+
+    /// The number of strings in the pool.
+    pub fn len(&self) -> usize {
+        self.lock().as_ref().map_or(0, HashSet::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops every string that is only referenced by the pool itself, returning how many were
+    /// removed.
+    pub fn trim(&self) -> usize {
+        let mut pool = self.lock();
+        let Some(pool) = pool.as_mut() else {
+            return 0;
+        };
+        let before = pool.len();
+        pool.retain(|s| Arc::strong_count(s) > 1);
+        pool.shrink_to_fit();
+        before - pool.len()
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,12 +1,65 @@
 //! Small representations for sets of (mostly) known strings using enums.
 use std::{
-    borrow::Borrow, cmp::Ordering, convert::Infallible, fmt, hash, ops::Deref, str::FromStr,
+    borrow::{Borrow, Cow},
+    cmp::Ordering,
+    convert::Infallible,
+    fmt, hash,
+    marker::PhantomData,
+    ops::Deref,
+    str::FromStr,
 };
 
+pub use collections::{EnumStrMap, EnumStrSet, SmollerMap};
+pub use intern::Interner;
 pub use smol_str::SmolStr;
 
 pub use smoller_str_macro::*;
 
+pub mod collections;
+pub mod intern;
+#[doc(hidden)]
+pub mod lookup;
+#[doc(hidden)]
+pub mod nested;
+#[cfg(feature = "search")]
+pub mod search;
+#[cfg(feature = "serde")]
+pub mod serde;
+
+/// The builtin value `$value` of the `#[smoller_str]` type `$ty`, as a constant.
+///
+/// Compilation fails if `$value` is not one of the builtin values.
+///
+/// ```
+/// use smoller_str::{smoller, smoller_strings};
+///
+/// smoller_strings!(Method, ["GET", "POST"]);
+///
+/// static GET: Method = smoller!(Method, "GET");
+/// assert_eq!(GET, Method::Builtin(MethodRepr::Get));
+/// ```
+///
+/// ```compile_fail
+/// use smoller_str::{smoller, smoller_strings};
+///
+/// smoller_strings!(Method, ["GET", "POST"]);
+///
+/// let put = smoller!(Method, "PUT");
+/// ```
+#[macro_export]
+macro_rules! smoller {
+    ($ty:ty, $value:literal $(,)?) => {{
+        const VALUE: $ty = <$ty>::try_builtin($value).expect(concat!(
+            "`",
+            $value,
+            "` is not a builtin value of `",
+            stringify!($ty),
+            "`"
+        ));
+        VALUE
+    }};
+}
+
 // #[macro_export]
 // macro_rules! include_smoller_strings {
 //     ($name:ident, $file:literal) => {
@@ -15,29 +68,265 @@ pub use smoller_str_macro::*;
 // }
 
 /// An enum representing a set of statically known strings.
+///
+/// `derive(EnumStr)` also takes tuple variants marked `#[nested]` or
+/// `#[nested(prefix = "...")]`, holding the values of another `EnumStr` type. Their values
+/// take the place of the variant in [`VALUES`](EnumStr::VALUES), with the prefix in front of
+/// their strings, and in front of their aliases in [`ALIASES`](EnumStr::ALIASES) and
+/// [`aliases`](EnumStr::aliases). A string that two values share is a compile-time error
+/// naming it. The nested type can come from any module, even the `Repr` of a [`SmollerStr`]
+/// type, whose own functions are private.
 pub trait EnumStr: FromStr + Copy + fmt::Display + 'static {
     const VALUES: &'static [Self];
+    /// Every alias together with the value it parses into, in declaration order.
+    const ALIASES: &'static [(&'static str, Self)] = &[];
+    /// Whether parsing and comparing with strings ignores ASCII case.
+    const CASE_INSENSITIVE: bool = false;
+    /// The number of values, so that `0..COUNT` are the valid [`index`](EnumStr::index)es.
+    const COUNT: usize = Self::VALUES.len();
     fn as_str(&self) -> &'static str;
+    /// The other spellings that parse into this value.
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// The position of this value in [`VALUES`](EnumStr::VALUES).
+    ///
+    /// The derive generates a `match`; the default searches `VALUES`.
+    fn index(&self) -> usize {
+        Self::VALUES
+            .iter()
+            .position(|value| value.as_str() == self.as_str())
+            .expect("value is missing from `EnumStr::VALUES`")
+    }
+
+    /// The value at `index` in [`VALUES`](EnumStr::VALUES), if there is one.
+    fn from_index(index: usize) -> Option<Self> {
+        Self::VALUES.get(index).copied()
+    }
+
+    /// The longest value (or alias) that `s` starts with, and the rest of `s`.
+    fn parse_prefix(s: &str) -> Option<(Self, &str)> {
+        let (value, rest) = Self::parse_prefix_bytes(s.as_bytes())?;
+        Some((value, &s[s.len() - rest.len()..]))
+    }
+
+    /// The longest value (or alias) that `bytes` starts with, and the rest of `bytes`.
+    ///
+    /// The derive generates a trie for this; the default checks every value in turn.
+    fn parse_prefix_bytes(bytes: &[u8]) -> Option<(Self, &[u8])> {
+        let values = Self::VALUES.iter().map(|&value| (value.as_str(), value));
+        values
+            .chain(Self::ALIASES.iter().copied())
+            .filter(|(s, _)| {
+                bytes.len() >= s.len()
+                    && if Self::CASE_INSENSITIVE {
+                        bytes[..s.len()].eq_ignore_ascii_case(s.as_bytes())
+                    } else {
+                        bytes.starts_with(s.as_bytes())
+                    }
+            })
+            .fold(None, |best: Option<(&str, Self)>, (s, value)| match best {
+                Some((longest, _)) if longest.len() >= s.len() => best,
+                _ => Some((s, value)),
+            })
+            .map(|(s, value)| (value, &bytes[s.len()..]))
+    }
+
+    /// The automaton used by [`find_iter`](EnumStr::find_iter), built the first time it is
+    /// needed.
+    #[cfg(feature = "search")]
+    fn searcher() -> &'static search::Searcher<Self>
+    where
+        Self: Send + Sync,
+    {
+        search::Searcher::shared()
+    }
+
+    /// Every non-overlapping occurrence of a value (or alias) in `text`, with its byte range.
+    ///
+    /// Where values overlap, the one starting first wins, then the longest.
+    #[cfg(feature = "search")]
+    fn find_iter(text: &str) -> search::FindIter<'static, '_, Self>
+    where
+        Self: Send + Sync,
+    {
+        Self::searcher().find_iter(text)
+    }
     // fn values(&self) -> &'static [Self];
 }
 
-pub trait SmollerStr: FromStr<Err = Infallible> + Clone + fmt::Display + 'static {
-    const BUILTIN: &'static [Self];
+/// An [`EnumStr`] type whose strings are compared exactly, which `derive(EnumStr)`
+/// implements unless `case_insensitive` is set.
+///
+/// [`Repr`] only implements `Borrow<str>` for these, since a case-insensitive `Eq` and `Hash`
+/// would disagree with those of `str`.
+pub trait CaseSensitive: EnumStr {}
+
+/// A string that is stored as an [`EnumStr`] value when it is one of the builtin values.
+///
+/// `FromStr` keeps unknown strings, unless the type was made with
+/// `#[smoller_str(unknown = "reject")]`, in which case it fails with [`UnknownValue`] and the
+/// type has no variant for them, so [`new`](SmollerStr::new) panics on them instead.
+/// Unknown strings go in the generated `Unknown` variant, or in the variant marked
+/// `#[fallback]`, whose single field can be any type with `From<&str>` and `AsRef<str>`,
+/// or [`SmolStr`].
+///
+/// Variants marked `#[fallback(prefix = "...")]`, `#[fallback(suffix = "...")]` or
+/// `#[fallback(with = path)]` take the strings matching their rule instead, trying each in
+/// order. The generated `kind()` then tells which variant a value is in.
+///
+/// A tuple variant marked `#[value(prefix = "...")]` keeps only the rest of the strings
+/// starting with its prefix. A type with such variants cannot lend out its full string, so
+/// instead of implementing this trait it gets the same methods inherently, with `as_str`
+/// returning a `Cow`, and implements [`SmollerKey`](collections::SmollerKey) so that it can
+/// still key a [`SmollerMap`]. The generated `split_prefix()` gives both halves without
+/// allocating.
+pub trait SmollerStr: FromStr + Clone + fmt::Display + 'static {
+    /// The set of builtin values.
+    type Builtin: EnumStr;
+
+    /// The builtin values.
+    #[deprecated(note = "use `Self::Builtin::VALUES` or `builtin_values()`")]
+    const BUILTIN: &'static [Self::Builtin] = <Self::Builtin as EnumStr>::VALUES;
 
     fn new<S: AsRef<str> + ?Sized>(value: &S) -> Self;
+    fn from_builtin(value: Self::Builtin) -> Self;
     fn as_str(&self) -> &str;
+    /// The builtin value, if this is one.
+    fn builtin(&self) -> Option<Self::Builtin>;
     fn is_heap_allocated(&self) -> bool;
-    fn is_builtin_value(&self) -> bool;
+    fn is_builtin_value(&self) -> bool {
+        self.builtin().is_some()
+    }
+
+    /// Like [`new`](SmollerStr::new), but fails instead of keeping a string that is not one
+    /// of the builtin values.
+    fn try_new<S: AsRef<str> + ?Sized>(value: &S) -> Result<Self, UnknownValue<'_, Self::Builtin>> {
+        let value = value.as_ref();
+        match value.parse() {
+            Ok(builtin) => Ok(Self::from_builtin(builtin)),
+            Err(_) => Err(UnknownValue::new(value)),
+        }
+    }
+
+    /// Every builtin value, in declaration order.
+    fn builtin_values() -> impl Iterator<Item = Self> {
+        Self::Builtin::VALUES
+            .iter()
+            .map(|&value| Self::from_builtin(value))
+    }
+}
+
+/// The error for a string that is not one of the values of `E`.
+///
+/// Borrows the rejected string, unless made [`'static`](UnknownValue::into_owned) for
+/// `FromStr`, whose error cannot borrow its input.
+pub struct UnknownValue<'a, E: EnumStr> {
+    input: Cow<'a, str>,
+    _expected: PhantomData<E>,
+}
+
+impl<'a, E: EnumStr> UnknownValue<'a, E> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input: Cow::Borrowed(input),
+            _expected: PhantomData,
+        }
+    }
+
+    /// Copies the rejected string, if it is borrowed.
+    pub fn into_owned(self) -> UnknownValue<'static, E> {
+        UnknownValue {
+            input: Cow::Owned(self.input.into_owned()),
+            _expected: PhantomData,
+        }
+    }
+
+    /// The rejected string.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The values that would have been accepted.
+    pub fn expected(&self) -> &'static [E] {
+        E::VALUES
+    }
 }
 
+/// Writes "one of `a`, `b`" for the values of `E`.
+pub(crate) fn write_expected<E: EnumStr>(f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("one of ")?;
+    for (i, value) in E::VALUES.iter().enumerate() {
+        if i != 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "`{}`", value.as_str())?;
+    }
+    Ok(())
+}
+
+impl<E: EnumStr> fmt::Display for UnknownValue<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown value `{}`, expected ", self.input)?;
+        write_expected::<E>(f)
+    }
+}
+
+impl<E: EnumStr> fmt::Debug for UnknownValue<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("UnknownValue")
+            .field("input", &self.input)
+            .field(
+                "expected",
+                &E::VALUES.iter().map(E::as_str).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<E: EnumStr> Clone for UnknownValue<'_, E> {
+    fn clone(&self) -> Self {
+        Self {
+            input: self.input.clone(),
+            _expected: PhantomData,
+        }
+    }
+}
+
+impl<E: EnumStr> PartialEq for UnknownValue<'_, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.input == other.input
+    }
+}
+
+impl<E: EnumStr> Eq for UnknownValue<'_, E> {}
+
+impl<E: EnumStr> std::error::Error for UnknownValue<'_, E> {}
+
+/// A generic [`SmollerStr`], for when `#[smoller_str]` is not wanted.
+///
+/// Behaves like the wrapper generated by `#[smoller_str(storage = SmolStr)]`.
 pub enum Repr<E: EnumStr> {
     Smol(SmolStr),
     Smoller(E),
 }
 
+fn str_eq<E: EnumStr>(a: &str, b: &str) -> bool {
+    if E::CASE_INSENSITIVE {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
 impl<E: EnumStr> hash::Hash for Repr<E> {
     fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
-        self.as_str().hash(hasher)
+        if E::CASE_INSENSITIVE {
+            lookup::hash_ignore_ascii_case(self.as_str(), hasher)
+        } else {
+            self.as_str().hash(hasher)
+        }
     }
 }
 
@@ -49,22 +338,28 @@ impl<E: EnumStr> Deref for Repr<E> {
     }
 }
 
-impl<E: EnumStr> Borrow<str> for Repr<E> {
+impl<E: CaseSensitive> Borrow<str> for Repr<E> {
     fn borrow(&self) -> &str {
         self.as_str()
     }
 }
 
+impl<E: EnumStr> AsRef<str> for Repr<E> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 macro_rules! partial_eq {
     ($t:ty, $self_fn:ident $(, $other_fn:ident)?) => {
         impl<E: EnumStr> PartialEq<$t> for Repr<E> {
             fn eq(&self, other: &$t) -> bool {
-                self.$self_fn() == other $(.$other_fn())?
+                str_eq::<E>(self.$self_fn(), other $(.$other_fn())?)
             }
         }
         impl<E: EnumStr> PartialEq<Repr<E>> for $t {
             fn eq(&self, other: &Repr<E>) -> bool {
-                self $(.$other_fn())? == other.$self_fn()
+                str_eq::<E>(self $(.$other_fn())?, other.$self_fn())
             }
         }
         impl<'a, E: EnumStr> PartialEq<&'a $t> for Repr<E> {
@@ -82,7 +377,7 @@ macro_rules! partial_eq {
 
 impl<E: EnumStr> PartialEq for Repr<E> {
     fn eq(&self, other: &Self) -> bool {
-        self.as_str() == other.as_str()
+        str_eq::<E>(self.as_str(), other.as_str())
     }
 }
 
@@ -94,7 +389,13 @@ impl<E: EnumStr> Eq for Repr<E> {}
 
 impl<E: EnumStr> Ord for Repr<E> {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.as_str().cmp(other.as_str())
+        if E::CASE_INSENSITIVE {
+            let lower = |b: u8| b.to_ascii_lowercase();
+            let other = other.as_str().bytes().map(lower);
+            self.as_str().bytes().map(lower).cmp(other)
+        } else {
+            self.as_str().cmp(other.as_str())
+        }
     }
 }
 
@@ -105,6 +406,14 @@ impl<E: EnumStr> PartialOrd for Repr<E> {
 }
 
 impl<E: EnumStr> Repr<E> {
+    pub fn new<S: AsRef<str> + ?Sized>(value: &S) -> Self {
+        let value = value.as_ref();
+        match value.parse() {
+            Ok(smoller) => Self::Smoller(smoller),
+            Err(_) => Self::Smol(SmolStr::new(value)),
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             Self::Smol(s) => s.as_str(),
@@ -113,6 +422,94 @@ impl<E: EnumStr> Repr<E> {
     }
 }
 
+impl<E: EnumStr> SmollerStr for Repr<E> {
+    type Builtin = E;
+
+    fn new<S: AsRef<str> + ?Sized>(value: &S) -> Self {
+        Repr::new(value)
+    }
+
+    fn from_builtin(value: E) -> Self {
+        Self::Smoller(value)
+    }
+
+    fn as_str(&self) -> &str {
+        Repr::as_str(self)
+    }
+
+    fn builtin(&self) -> Option<E> {
+        match self {
+            Self::Smol(_) => None,
+            Self::Smoller(e) => Some(*e),
+        }
+    }
+
+    fn is_heap_allocated(&self) -> bool {
+        match self {
+            Self::Smol(s) => s.is_heap_allocated(),
+            Self::Smoller(_) => false,
+        }
+    }
+}
+
+impl<E: EnumStr> Clone for Repr<E> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Smol(s) => Self::Smol(s.clone()),
+            Self::Smoller(e) => Self::Smoller(*e),
+        }
+    }
+}
+
+impl<E: EnumStr> FromStr for Repr<E> {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(s))
+    }
+}
+
+impl<E: EnumStr> From<E> for Repr<E> {
+    fn from(value: E) -> Self {
+        Self::Smoller(value)
+    }
+}
+
+impl<E: EnumStr> From<&str> for Repr<E> {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<E: EnumStr> From<String> for Repr<E> {
+    fn from(value: String) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl<E: EnumStr> From<SmolStr> for Repr<E> {
+    fn from(value: SmolStr) -> Self {
+        match value.parse() {
+            Ok(smoller) => Self::Smoller(smoller),
+            Err(_) => Self::Smol(value),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: EnumStr> ::serde::Serialize for Repr<E> {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::serialize(self.as_str(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, E: EnumStr> ::serde::Deserialize<'de> for Repr<E> {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde::deserialize_smoller_str(deserializer)
+    }
+}
+
 impl<E: EnumStr> fmt::Debug for Repr<E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Debug::fmt(self.as_str(), f)
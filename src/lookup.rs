@@ -0,0 +1,174 @@
+//! Support code for the generated `new` functions and the `case_insensitive` option.
+//!
+//! The hash has to produce exactly the same values as the one used by `smoller_str_macro` to
+//! build the `lookup = "phf"` tables.
+use std::hash::Hasher;
+
+pub struct Hashes {
+    pub g: u32,
+    pub f1: u32,
+    pub f2: u32,
+}
+
+pub const fn hash(bytes: &[u8], seed: u64, ignore_case: bool) -> Hashes {
+    let mut h = 0xcbf2_9ce4_8422_2325 ^ seed;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = if ignore_case {
+            bytes[i].to_ascii_lowercase()
+        } else {
+            bytes[i]
+        };
+        h ^= b as u64;
+        h = h.wrapping_mul(0x0100_0000_01b3);
+        i += 1;
+    }
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^= h >> 33;
+    Hashes {
+        g: (h >> 43) as u32,
+        f1: ((h >> 22) & 0x1f_ffff) as u32,
+        f2: (h & 0x3f_ffff) as u32,
+    }
+}
+
+pub const fn displace(f1: u32, f2: u32, d1: u32, d2: u32) -> u32 {
+    d2.wrapping_add(f1.wrapping_mul(d1)).wrapping_add(f2)
+}
+
+/// Index into the table of `len` slots where `bytes` would be stored.
+pub const fn slot(
+    bytes: &[u8],
+    seed: u64,
+    ignore_case: bool,
+    displacements: &[(u32, u32)],
+    len: usize,
+) -> usize {
+    let hashes = hash(bytes, seed, ignore_case);
+    let (d1, d2) = displacements[hashes.g as usize % displacements.len()];
+    displace(hashes.f1, hashes.f2, d1, d2) as usize % len
+}
+
+pub const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+pub const fn eq_ignore_ascii_case(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if !a[i].eq_ignore_ascii_case(&b[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// The byte at `i`, lowercased when ignoring case, for the `parse_prefix_bytes` trie.
+pub const fn byte_at(bytes: &[u8], i: usize, ignore_case: bool) -> Option<u8> {
+    if i >= bytes.len() {
+        None
+    } else if ignore_case {
+        Some(bytes[i].to_ascii_lowercase())
+    } else {
+        Some(bytes[i])
+    }
+}
+
+/// Hashes `s` so that strings differing only by ASCII case collide, in the same way
+/// `<str as Hash>::hash` terminates the bytes with `0xff`.
+pub fn hash_ignore_ascii_case<H: Hasher>(s: &str, hasher: &mut H) {
+    for b in s.bytes() {
+        hasher.write_u8(b.to_ascii_lowercase());
+    }
+    hasher.write_u8(0xff);
+}
+
+/// Hashes `prefix` followed by `rest` like `<str as Hash>::hash` does the joined string with a
+/// streaming hasher, for `#[value(prefix = "...")]` variants.
+pub fn hash_split<H: Hasher>(prefix: &str, rest: &str, ignore_case: bool, hasher: &mut H) {
+    if ignore_case {
+        for b in prefix.bytes().chain(rest.bytes()) {
+            hasher.write_u8(b.to_ascii_lowercase());
+        }
+    } else {
+        hasher.write(prefix.as_bytes());
+        hasher.write(rest.as_bytes());
+    }
+    hasher.write_u8(0xff);
+}
+
+/// Whether two strings given as a prefix and the rest are equal, without joining them.
+pub fn eq_split(a: (&str, &str), b: (&str, &str), ignore_case: bool) -> bool {
+    fn bytes<'a>((prefix, rest): (&'a str, &'a str)) -> impl Iterator<Item = u8> + 'a {
+        prefix.bytes().chain(rest.bytes())
+    }
+    if ignore_case {
+        let lower = |b: u8| b.to_ascii_lowercase();
+        bytes(a).map(lower).eq(bytes(b).map(lower))
+    } else {
+        bytes(a).eq(bytes(b))
+    }
+}
+
+/// Whether `s` starts with `prefix`, for `#[fallback(prefix = "...")]` variants.
+pub const fn has_prefix(s: &str, prefix: &str, ignore_case: bool) -> bool {
+    has_prefix_bytes(s.as_bytes(), prefix, ignore_case)
+}
+
+/// Whether `s` starts with `prefix`, for the `parse_prefix_bytes` of `#[nested]` variants.
+pub const fn has_prefix_bytes(s: &[u8], prefix: &str, ignore_case: bool) -> bool {
+    let prefix = prefix.as_bytes();
+    if s.len() < prefix.len() {
+        return false;
+    }
+    let (head, _) = s.split_at(prefix.len());
+    if ignore_case {
+        eq_ignore_ascii_case(head, prefix)
+    } else {
+        bytes_eq(head, prefix)
+    }
+}
+
+/// Whether `s` ends with `suffix`, for `#[fallback(suffix = "...")]` variants.
+pub const fn has_suffix(s: &str, suffix: &str, ignore_case: bool) -> bool {
+    let (s, suffix) = (s.as_bytes(), suffix.as_bytes());
+    if s.len() < suffix.len() {
+        return false;
+    }
+    let (_, tail) = s.split_at(s.len() - suffix.len());
+    if ignore_case {
+        eq_ignore_ascii_case(tail, suffix)
+    } else {
+        bytes_eq(tail, suffix)
+    }
+}
+
+/// Whether `s` is `prefix` followed by `suffix`, for `#[value(prefix = "...")]` variants.
+pub const fn eq_prefixed(s: &str, prefix: &str, suffix: &str, ignore_case: bool) -> bool {
+    if s.len() != prefix.len() + suffix.len() || !has_prefix(s, prefix, ignore_case) {
+        return false;
+    }
+    let (_, rest) = s.as_bytes().split_at(prefix.len());
+    if ignore_case {
+        eq_ignore_ascii_case(rest, suffix.as_bytes())
+    } else {
+        bytes_eq(rest, suffix.as_bytes())
+    }
+}
@@ -0,0 +1,86 @@
+//! Support code for the `#[nested(prefix = "...")]` variants of `derive(EnumStr)`.
+//!
+//! The strings of the nested values are written after the prefix into one buffer at compile
+//! time, so that the generated `as_str` can still return a `&'static str`.
+//!
+//! `smoller_strings!` turns each `..Type` into a `#[nested]` variant holding the values of
+//! [`Values::EnumStr`].
+
+/// The `EnumStr` type whose values `..Self` stands for in `smoller_strings!`: the type itself,
+/// or the `Repr` of a `#[smoller_str]` type.
+pub trait Values {
+    type EnumStr: crate::EnumStr;
+}
+
+/// The length of the buffer holding each of `strs` after `prefix`.
+pub const fn buffer_len(prefix: &str, strs: &[&str]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i < strs.len() {
+        len += prefix.len() + strs[i].len();
+        i += 1;
+    }
+    len
+}
+
+/// Each of `strs` after `prefix`, one after the other.
+pub const fn buffer<const LEN: usize>(prefix: &str, strs: &[&str]) -> [u8; LEN] {
+    let mut buffer = [0; LEN];
+    let mut at = 0;
+    let mut i = 0;
+    while i < strs.len() {
+        at = copy(&mut buffer, at, prefix.as_bytes());
+        at = copy(&mut buffer, at, strs[i].as_bytes());
+        i += 1;
+    }
+    buffer
+}
+
+const fn copy(buffer: &mut [u8], at: usize, bytes: &[u8]) -> usize {
+    let mut i = 0;
+    while i < bytes.len() {
+        buffer[at + i] = bytes[i];
+        i += 1;
+    }
+    at + bytes.len()
+}
+
+/// Where each string of the [`buffer`] starts, followed by where the last one ends.
+pub const fn offsets<const N: usize>(prefix: &str, strs: &[&str]) -> [usize; N] {
+    let mut offsets = [0; N];
+    let mut i = 0;
+    while i < strs.len() {
+        offsets[i + 1] = offsets[i] + prefix.len() + strs[i].len();
+        i += 1;
+    }
+    offsets
+}
+
+/// The `i`th string of a [`buffer`].
+pub const fn str_at(buffer: &'static [u8], offsets: &[usize], i: usize) -> &'static str {
+    let (bytes, _) = buffer.split_at(offsets[i + 1]);
+    let (_, bytes) = bytes.split_at(offsets[i]);
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(_) => panic!("a prefix split a character in two"),
+    }
+}
+
+/// Every string of a [`buffer`], for slicing.
+pub const fn strs<const N: usize>(buffer: &'static [u8], offsets: &[usize]) -> [&'static str; N] {
+    let mut strs = [""; N];
+    let mut i = 0;
+    while i < N {
+        strs[i] = str_at(buffer, offsets, i);
+        i += 1;
+    }
+    strs
+}
+
+/// The panic message written into a [`buffer`] with an empty prefix.
+pub const fn message(buffer: &[u8]) -> &str {
+    match std::str::from_utf8(buffer) {
+        Ok(s) => s,
+        Err(_) => panic!("a collision message is not UTF-8"),
+    }
+}
@@ -0,0 +1,99 @@
+//! Finding the builtin values of an [`EnumStr`] inside longer text.
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    ops::Range,
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+use aho_corasick::{AhoCorasick, MatchKind};
+
+use crate::EnumStr;
+
+/// An Aho-Corasick automaton matching every value and alias of `E`.
+///
+/// Built once per type by [`EnumStr::searcher`].
+pub struct Searcher<E: EnumStr> {
+    automaton: AhoCorasick,
+    /// The value for each pattern of the automaton.
+    values: Vec<E>,
+}
+
+impl<E: EnumStr> Searcher<E> {
+    /// Builds the automaton. Overlapping values are resolved by taking the leftmost match,
+    /// and the longest one of those.
+    pub fn new() -> Self {
+        let patterns = E::VALUES
+            .iter()
+            .map(|&value| (value.as_str(), value))
+            .chain(E::ALIASES.iter().copied());
+        let (patterns, values): (Vec<_>, Vec<_>) = patterns.unzip();
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .ascii_case_insensitive(E::CASE_INSENSITIVE)
+            .build(patterns)
+            .expect("the values of an `EnumStr` are too large to search for");
+        Self { automaton, values }
+    }
+
+    /// Every non-overlapping occurrence of a value in `text`, with its byte range.
+    pub fn find_iter<'s, 'h>(&'s self, text: &'h str) -> FindIter<'s, 'h, E> {
+        FindIter {
+            inner: self.automaton.find_iter(text),
+            values: &self.values,
+        }
+    }
+
+    /// Used by the default [`EnumStr::searcher`], which cannot have a `static` per type. The
+    /// derived impls have their own `static` instead, so this only holds the searchers of types
+    /// implemented by hand, each built once and kept for the rest of the program.
+    pub(crate) fn shared() -> &'static Self
+    where
+        E: Send + Sync,
+    {
+        static SEARCHERS: OnceLock<Mutex<HashMap<TypeId, &'static (dyn Any + Sync + Send)>>> =
+            OnceLock::new();
+        // The map is only ever inserted into, so it is still whole after a panic.
+        let mut searchers = SEARCHERS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let searcher = *searchers
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| Box::leak(Box::new(Self::new())));
+        searcher.downcast_ref().unwrap()
+    }
+}
+
+impl<E: EnumStr> Default for Searcher<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: EnumStr> fmt::Debug for Searcher<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Searcher")
+            .field(
+                "values",
+                &self.values.iter().map(E::as_str).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// The iterator returned by [`EnumStr::find_iter`].
+pub struct FindIter<'s, 'h, E: EnumStr> {
+    inner: aho_corasick::FindIter<'s, 'h>,
+    values: &'s [E],
+}
+
+impl<E: EnumStr> Iterator for FindIter<'_, '_, E> {
+    type Item = (Range<usize>, E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let m = self.inner.next()?;
+        Some((m.range(), self.values[m.pattern().as_usize()]))
+    }
+}
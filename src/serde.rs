@@ -0,0 +1,79 @@
+//! `serde` support for [`EnumStr`] and [`SmollerStr`] types.
+//!
+//! The impls themselves are generated by `derive(EnumStr)` and `#[smoller_str]` when the
+//! `serde` feature is enabled; they forward to the helpers in this module.
+use std::{fmt, marker::PhantomData};
+
+pub use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{collections::SmollerKey, EnumStr};
+
+pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(value)
+}
+
+/// Deserialize one of the values of `E`, failing on any other string.
+pub fn deserialize_enum_str<'de, E: EnumStr, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<E, D::Error> {
+    struct Visitor<E>(PhantomData<E>);
+
+    impl<'de, E: EnumStr> de::Visitor<'de> for Visitor<E> {
+        type Value = E;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            crate::write_expected::<E>(f)
+        }
+
+        fn visit_str<Err: de::Error>(self, v: &str) -> Result<E, Err> {
+            v.parse()
+                .map_err(|_| Err::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+
+    deserializer.deserialize_str(Visitor(PhantomData))
+}
+
+/// Deserialize a `T`, only allocating when the string is not one of the builtin values.
+pub fn deserialize_smoller_str<'de, T: SmollerKey, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    struct Visitor<T>(PhantomData<T>);
+
+    impl<'de, T: SmollerKey> de::Visitor<'de> for Visitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a string")
+        }
+
+        fn visit_str<Err: de::Error>(self, v: &str) -> Result<T, Err> {
+            v.parse()
+                .map_err(|_| Err::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+
+    deserializer.deserialize_str(Visitor(PhantomData))
+}
+
+/// Deserialize a `T` made with `unknown = "reject"`, failing on the strings it does not keep.
+pub fn deserialize_known_smoller_str<'de, T: SmollerKey, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    struct Visitor<T>(PhantomData<T>);
+
+    impl<'de, T: SmollerKey> de::Visitor<'de> for Visitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            crate::write_expected::<T::Builtin>(f)
+        }
+
+        fn visit_str<Err: de::Error>(self, v: &str) -> Result<T, Err> {
+            v.parse()
+                .map_err(|_| Err::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+
+    deserializer.deserialize_str(Visitor(PhantomData))
+}
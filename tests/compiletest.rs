@@ -1,5 +1,5 @@
-// #[test]
-// fn ui() {
-//     let t = trybuild::TestCases::new();
-//     t.compile_fail("ui/*.rs");
-// }
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
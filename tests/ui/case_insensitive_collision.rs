@@ -0,0 +1,12 @@
+use smoller_str::EnumStr;
+
+#[derive(EnumStr, Clone, Copy)]
+#[enum_str(case_insensitive)]
+pub enum Header {
+    #[value("Content-Type")]
+    ContentType,
+    #[value("content-type")]
+    ContentTypeLower,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use smoller_str::smoller_strings;
+
+smoller_strings!(Words, ["Foo Bar", "foo bar", "BAZ" = Baz, "baz"]);
+
+smoller_strings!(Headers, ["Content-Type", "content type"]);
+
+smoller_strings!(Dups, ["a", "b", "a"]);
+
+fn main() {}
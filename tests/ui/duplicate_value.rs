@@ -0,0 +1,13 @@
+use smoller_str::EnumStr;
+
+#[derive(EnumStr, Clone, Copy)]
+pub enum Method {
+    #[value("GET")]
+    Get,
+    #[value("POST", alias = "GET")]
+    Post,
+    #[value("POST")]
+    PostAgain,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use smoller_str::EnumStr;
+
+#[derive(EnumStr, Clone, Copy)]
+pub enum Method {
+    #[value("")]
+    Empty,
+}
+
+fn main() {}
@@ -0,0 +1,75 @@
+use smoller_str::{smoller_str, EnumStr};
+
+#[smoller_str]
+#[derive(EnumStr, Clone, Copy)]
+pub enum Unmarked {
+    #[value("a")]
+    A,
+    Other(String),
+}
+
+#[smoller_str]
+#[derive(EnumStr, Clone, Copy)]
+pub enum Twice {
+    #[value("a")]
+    A,
+    #[fallback]
+    First(String),
+    #[fallback]
+    Second(String),
+}
+
+#[smoller_str]
+#[derive(EnumStr, Clone, Copy)]
+pub enum Unit {
+    #[value("a")]
+    A,
+    #[fallback]
+    Other,
+}
+
+#[smoller_str(storage = Box)]
+#[derive(EnumStr, Clone, Copy)]
+pub enum WithStorage {
+    #[value("a")]
+    A,
+    #[fallback]
+    Other(String),
+}
+
+#[smoller_str]
+#[derive(EnumStr, Clone, Copy)]
+pub enum BadRules {
+    #[value("a")]
+    A,
+    #[fallback(prefix = "")]
+    Empty(String),
+    #[fallback(start = "x")]
+    Unknown(String),
+    #[fallback(with = "f")]
+    NotPath(String),
+    #[fallback(prefix = "x", suffix = "y")]
+    Both(String),
+}
+
+#[smoller_str]
+#[derive(EnumStr, Clone, Copy)]
+pub enum Reserved {
+    #[value("a")]
+    A,
+    #[fallback(prefix = "x-")]
+    Unknown(String),
+    #[value(prefix = "y-")]
+    Builtin(String),
+}
+
+#[smoller_str]
+#[derive(EnumStr, Clone, Copy)]
+pub enum CatchAllBuiltin {
+    #[value("a")]
+    A,
+    #[fallback]
+    Builtin(String),
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+use smoller_str::EnumStr;
+
+#[derive(EnumStr, Clone, Copy)]
+pub enum Method {
+    #[value("GET")]
+    Get,
+    #[value("POST", alias = "post")]
+    Post,
+}
+
+#[derive(EnumStr, Clone, Copy)]
+pub enum Collides {
+    #[value("GET")]
+    Get,
+    #[nested]
+    Method(Method),
+}
+
+#[derive(EnumStr, Clone, Copy)]
+pub enum AliasCollides {
+    #[value("post")]
+    Post,
+    #[nested]
+    Method(Method),
+}
+
+#[derive(EnumStr, Clone, Copy)]
+pub enum PrefixedAliasCollides {
+    #[value("m.post")]
+    Post,
+    #[nested(prefix = "m.")]
+    Method(Method),
+}
+
+#[derive(EnumStr, Clone, Copy)]
+pub enum Unit {
+    #[nested]
+    Method,
+}
+
+#[derive(EnumStr, Clone, Copy)]
+pub enum WithValue {
+    #[nested]
+    #[value("x")]
+    Method(Method),
+}
+
+fn main() {}
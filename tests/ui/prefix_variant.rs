@@ -0,0 +1,25 @@
+use smoller_str::{smoller_str, EnumStr, SmolStr};
+
+#[smoller_str]
+#[derive(EnumStr, Clone, Copy)]
+pub enum Duplicate {
+    #[value(prefix = "x-")]
+    A(SmolStr),
+    #[value(prefix = "x-")]
+    B(SmolStr),
+}
+
+#[smoller_str]
+#[derive(EnumStr, Clone, Copy)]
+pub enum Empty {
+    #[value(prefix = "")]
+    A(SmolStr),
+}
+
+#[derive(EnumStr)]
+pub enum NotSmoller {
+    #[value(prefix = "x-")]
+    A(SmolStr),
+}
+
+fn main() {}
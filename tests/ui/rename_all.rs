@@ -0,0 +1,22 @@
+use smoller_str::EnumStr;
+
+#[derive(EnumStr, Clone, Copy)]
+#[enum_str(rename_all = "kebab")]
+pub enum Invalid {
+    A,
+}
+
+#[derive(EnumStr, Clone, Copy)]
+#[enum_str(rename_all = "lowercase")]
+pub enum Collides {
+    #[value("ab")]
+    First,
+    AB,
+}
+
+#[derive(EnumStr, Clone, Copy)]
+pub enum Missing {
+    NoValue,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use smoller_str::{smoller, smoller_strings};
+
+smoller_strings!(Method, ["GET", "POST"]);
+
+fn main() {
+    let _ = smoller!(Method, "PUT");
+}
@@ -0,0 +1,10 @@
+use smoller_str::smoller_strings;
+
+smoller_strings!(Methods, ["GET", "POST"]);
+smoller_strings!(Verbs, ["GET", "PUT"]);
+
+smoller_strings!(Collides, [..Methods, "PUT", ..Verbs]);
+
+smoller_strings!(SameName, ["methods", ..Methods]);
+
+fn main() {}
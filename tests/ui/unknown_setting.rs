@@ -0,0 +1,26 @@
+use smoller_str::{smoller_str, EnumStr};
+
+#[smoller_str(unknown = "drop")]
+#[derive(EnumStr, Clone, Copy)]
+pub enum Method {
+    #[value("GET")]
+    Get,
+}
+
+#[smoller_str(unknown = "reject")]
+#[derive(EnumStr, Clone, Copy)]
+pub enum Strict {
+    #[value("a")]
+    A,
+    #[fallback]
+    Rest(String),
+}
+
+#[smoller_str(storage = Arc, unknown = "reject")]
+#[derive(EnumStr, Clone, Copy)]
+pub enum Stored {
+    #[value("a")]
+    A,
+}
+
+fn main() {}
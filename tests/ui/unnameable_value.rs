@@ -0,0 +1,7 @@
+use smoller_str::smoller_strings;
+
+smoller_strings!(Emoji, ["🦀", "🦀🦀" = Crab]);
+
+smoller_strings!(Case, ["a"], ident_case = "kebab");
+
+fn main() {}